@@ -26,7 +26,7 @@ fn bench_sudoku(c: &mut Criterion) {
     c.bench_function("Sudoku", |b| {
         b.iter(|| {
             for sudoku in sudokus.iter_mut() {
-                let field = sudoku.field;
+                let field = sudoku.field.clone();
                 sudoku.solve();
                 sudoku.field = field;
             }
@@ -1,20 +1,147 @@
 use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
 
-use sudoku::sudokus::Sudoku;
+use sudoku::sudokus::{Booklet, FileFormat, MultiSudoku, ParseError, RuleRegistry, Sudoku};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
+    // A booklet of many Sudokus is written as LaTeX
+    if args.len() > 1 && args[1] == "--booklet" {
+        booklet(&args[2..]);
+        return;
+    }
+
     if args.len() != 2 {
         println!("Please Provide a single file of a Sudoku to solve");
         return;
     }
     
-    let mut sudoku = Sudoku::from_file(&args[1]);
+    // Pictures are read and the numbers are written as text
+    if args[1].ends_with(".png") {
+        let mut sudoku = Sudoku::from_picture(&args[1]).unwrap_or_else(|error| panic!("{}", error));
+
+        if sudoku.solve() {
+            println!("Solved the Sudoku: ");
+        }
+
+        print(&sudoku);
+        return;
+    }
+
+    let content = fs::read_to_string(&args[1]).expect("Unable to read the file");
+
+    // Multiple grids are solved together
+    if content.contains("grid:") {
+        let mut sudoku = MultiSudoku::parse_with_registry(&content, &RuleRegistry::default())
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        if sudoku.solve() {
+            println!("Solved the Sudoku: ");
+        }
+
+        println!("{sudoku}");
+        return;
+    }
+
+    // Puzzles of f-puzzles and SudokuPad are JSON or a link
+    if args[1].ends_with(".json") || content.trim_start().starts_with("http") {
+        let mut sudoku = Sudoku::from_f_puzzles(&content).unwrap_or_else(|error| panic!("{}", error));
+
+        if sudoku.solve() {
+            println!("Solved the Sudoku: ");
+        }
+
+        print(&sudoku);
+        return;
+    }
+
+    let format = FileFormat::recognize(&args[1], &content);
+
+    // Files of other programs are written in the same format again
+    if let Some(format) = format.filter(|&format| format != FileFormat::Sdm) {
+        let mut sudoku = Sudoku::parse_format(&content, format).unwrap_or_else(|error| panic!("{}", error));
+        sudoku.solve();
+        print!("{}", sudoku.to_format(format));
+        return;
+    }
+
+    // Files without sections have a Sudoku in every line
+    if format == Some(FileFormat::Sdm) || !content.contains(':') {
+        for sudoku in Sudoku::lines_from_file(&args[1]).expect("Unable to read the file") {
+            let mut sudoku = sudoku.unwrap_or_else(|error| panic!("{}", error));
+            sudoku.solve();
+            println!("{}", sudoku.to_line());
+        }
+        return;
+    }
+
+    let mut sudoku = Sudoku::parse(&content);
 
     if sudoku.solve() {
         println!("Solved the Sudoku: ");
     }
 
-    println!("{sudoku}");
+    print(&sudoku);
+}
+
+/// Prints the Sudoku with borders and its moves. Colors are only used in a terminal.
+fn print(sudoku: &Sudoku) {
+    print!("{}", sudoku.pretty().colors(io::stdout().is_terminal()));
+}
+
+/// Prints a LaTeX booklet of the Sudokus of all files.
+/// The arguments are `[--solutions] [--title <title>] <files>...`
+fn booklet(args: &[String]) {
+    let mut solutions = false;
+    let mut title = "Sudoku".to_string();
+    let mut paths = vec![];
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--solutions" => solutions = true,
+            "--title" => title = args.next().expect("The title is missing").clone(),
+            _ => paths.push(arg),
+        }
+    }
+
+    if paths.is_empty() {
+        println!("Please Provide the files of the Sudokus for the booklet");
+        return;
+    }
+
+    let mut booklet = Booklet::new(&title, solutions);
+    for path in paths {
+        for sudoku in load(path).unwrap_or_else(|error| panic!("{}: {}", path, error)) {
+            booklet.add(sudoku, Booklet::difficulty(path));
+        }
+    }
+    print!("{}", booklet.to_latex());
+}
+
+/// Returns all Sudokus of a file in any of the supported formats
+fn load(path: &str) -> Result<Vec<Sudoku>, ParseError> {
+    if path.ends_with(".png") {
+        return Ok(vec![Sudoku::from_picture(path)?]);
+    }
+
+    let content = fs::read_to_string(path).map_err(|error| ParseError::Io(error.to_string()))?;
+
+    // Multiple grids can't be drawn
+    if content.contains("grid:") {
+        return Err(ParseError::Unsupported("grid".to_string()));
+    }
+
+    if path.ends_with(".json") || content.trim_start().starts_with("http") {
+        return Ok(vec![Sudoku::from_f_puzzles(&content)?]);
+    }
+
+    match FileFormat::recognize(path, &content) {
+        Some(FileFormat::Sdm) => Sudoku::lines_from_file(path)?.collect(),
+        Some(format) => Ok(vec![Sudoku::parse_format(&content, format)?]),
+        None if !content.contains(':') => Sudoku::lines_from_file(path)?.collect(),
+        None => Ok(vec![Sudoku::parse_with_registry(&content, &RuleRegistry::default())?]),
+    }
 }
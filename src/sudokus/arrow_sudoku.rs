@@ -0,0 +1,212 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{
+    all_numbers, candidates, invalid_section, parse_cells, reachable_sums, section_content,
+    SudokuRule,
+};
+
+/// A single arrow. The number in the circle is the sum of the numbers on the arrow
+#[derive(Debug, Clone)]
+struct Arrow {
+    circle: (usize, usize),
+    path: Vec<(usize, usize)>,
+}
+
+/// Arrow Sudoku
+///
+/// Every arrow is written in its own line of the `arrow` section.
+/// The first cell is the circle, the others are the cells of the arrow:
+/// ```text
+/// arrow:
+/// r1c1 r1c2 r1c3
+/// r5c5 r6c6;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArrowSudoku {
+    arrows: Vec<Arrow>,
+}
+
+impl SudokuRule for ArrowSudoku {
+    fn str_identifier() -> &'static str {
+        "arrow"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let mut arrows = vec![];
+        for line in section_content(input, Self::str_identifier()).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let cells = parse_cells(line, geometry.size()).ok_or_else(|| {
+                invalid_section(Self::str_identifier(), format!("Invalid cell in {}", line))
+            })?;
+            if cells.len() < 2 {
+                return Err(invalid_section(
+                    Self::str_identifier(),
+                    "An arrow needs a circle and at least one cell",
+                ));
+            }
+
+            arrows.push(Arrow {
+                circle: cells[0],
+                path: cells[1..].to_vec(),
+            });
+        }
+
+        Ok(Box::new(Self { arrows }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for arrow in &self.arrows {
+            let mut sum = 0;
+            let mut unset = 0;
+            for &(row, collum) in &arrow.path {
+                if field[row][collum].count_ones() != 1 {
+                    unset += 1;
+                    continue;
+                }
+                sum += field[row][collum].trailing_zeros() as i32 + 1;
+            }
+
+            let (row, collum) = arrow.circle;
+            if field[row][collum].count_ones() != 1 {
+                // Every unset cell on the arrow adds at least 1
+                if sum + unset > field.len() as i32 {
+                    return false;
+                }
+                continue;
+            }
+
+            let circle = field[row][collum].trailing_zeros() as i32 + 1;
+            if sum + unset > circle || (unset == 0 && sum != circle) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let size = field.len() as i32;
+        let mut possible = all_numbers(field);
+
+        for arrow in &self.arrows {
+            if arrow.circle == (row, collum) {
+                let sums = reachable_sums(arrow.path.iter().map(|&(r, c)| candidates(field, r, c)));
+
+                let mut possible_in_circle = 0;
+                for num in 1..=size {
+                    if sums.contains(num) {
+                        possible_in_circle |= 0b1 << (num - 1);
+                    }
+                }
+                possible &= possible_in_circle;
+                continue;
+            }
+
+            if !arrow.path.contains(&(row, collum)) {
+                continue;
+            }
+
+            let others = reachable_sums(
+                arrow
+                    .path
+                    .iter()
+                    .filter(|&&cell| cell != (row, collum))
+                    .map(|&(r, c)| candidates(field, r, c)),
+            );
+            let circle = candidates(field, arrow.circle.0, arrow.circle.1);
+
+            let mut possible_on_arrow = 0;
+            for num in 1..=size {
+                for sum in num + 1..=size {
+                    if circle & (0b1 << (sum - 1)) != 0 && others.contains(sum - num) {
+                        possible_on_arrow |= 0b1 << (num - 1);
+                        break;
+                    }
+                }
+            }
+            possible &= possible_on_arrow;
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        for arrow in &self.arrows {
+            let (x, y) = Drawing::center(arrow.circle);
+            drawing.circle((x, y), 0.4, "none", "#808080");
+
+            // The arrow starts at the border of the circle
+            let mut points: Vec<_> = arrow.path.iter().map(|&cell| Drawing::center(cell)).collect();
+            let (first_x, first_y) = points[0];
+            let length = (first_x - x).hypot(first_y - y);
+            points.insert(0, (x + (first_x - x) / length * 0.4, y + (first_y - y) / length * 0.4));
+            drawing.arrow(&points, "#808080", 0.04);
+        }
+    }
+}
+
+#[cfg(test)]
+mod arrow_sudoku_test {
+    use super::ArrowSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    // Numbers from: https://de.wikipedia.org/wiki/Sudoku#/media/Datei:Sudoku_variant.png
+    const FIELD: [[u32; 9]; 9] = [
+        [1, 2, 3, 7, 8, 9, 4, 5, 6],
+        [4, 5, 6, 1, 2, 3, 7, 8, 9],
+        [7, 8, 9, 4, 5, 6, 1, 2, 3],
+        [2, 3, 1, 8, 9, 7, 5, 6, 4],
+        [5, 6, 4, 2, 3, 1, 8, 9, 7],
+        [8, 9, 7, 5, 6, 4, 2, 3, 1],
+        [3, 1, 2, 9, 7, 8, 6, 4, 5],
+        [6, 4, 5, 3, 1, 2, 9, 7, 8],
+        [9, 7, 8, 6, 4, 5, 3, 1, 2],
+    ];
+
+    fn raw(field: [[u32; 9]; 9]) -> Vec<Vec<u32>> {
+        field.map(|r| r.map(|n| if n == 0 { 0 } else { 0b1 << (n - 1) }).to_vec()).to_vec()
+    }
+
+    #[test]
+    fn comply() {
+        // field[0][2] = 3 = field[0][0] + field[0][1]
+        let good = ArrowSudoku::from_str("arrow:\nr3c1 r1c1 r2c1", Geometry::CLASSIC);
+        // field[0][3] = 7 != 1 + 2
+        let bad = ArrowSudoku::from_str("arrow:\nr4c1 r1c1 r2c1", Geometry::CLASSIC);
+
+        assert!(good.complys(&raw(FIELD)));
+        assert!(!bad.complys(&raw(FIELD)));
+
+        // A partial field can't be wrong as long as the sum still fits
+        let mut partial = FIELD;
+        partial[0][0] = 0;
+        assert!(good.complys(&raw(partial)));
+    }
+
+    #[test]
+    fn not_possible() {
+        let arrow = ArrowSudoku::from_str("arrow:\nr1c1 r1c2 r1c3", Geometry::CLASSIC);
+        let mut field = vec![vec![0; 9]; 9];
+
+        // Two cells sum up to at least 2
+        assert_eq!(arrow.get_not_possible_numbers_raw(&field, 0, 0), 0b1);
+
+        // With 3 on the arrow the circle must be at least 4 and the other cell at most 6
+        field[1][0] = 0b100;
+        assert_eq!(arrow.get_not_possible_numbers_raw(&field, 0, 0), 0b111);
+        assert_eq!(arrow.get_not_possible_numbers_raw(&field, 2, 0), 0b111_000_000);
+
+        // Cells not on the arrow are not affected
+        assert_eq!(arrow.get_not_possible_numbers_raw(&field, 4, 4), 0);
+    }
+}
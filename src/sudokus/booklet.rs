@@ -0,0 +1,210 @@
+use super::drawing::{escape_latex, Drawing};
+use super::sudoku::Sudoku;
+
+/// Width of a puzzle on the page in centimeters, with the margin for clues outside of the field
+const PUZZLE_WIDTH: f64 = 10.0;
+
+/// Width of a solution on the page in centimeters
+const SOLUTION_WIDTH: f64 = 6.5;
+
+/// Levels used in the names of the Sudoku files, see `Sudoku::UNSOLVED_SUDOKU_FILES`.
+/// `extra-hard` is checked before `hard`.
+const DIFFICULTIES: [&str; 5] = ["extra-hard", "easy", "medium", "hard", "master"];
+
+/// A puzzle of the booklet with its solution
+struct Puzzle {
+    puzzle: Drawing,
+    /// The drawing of the solution or none if the Sudoku couldn't be solved
+    solution: Option<Drawing>,
+    difficulty: Option<String>,
+}
+
+/// A printable booklet of Sudokus as LaTeX document.
+///
+/// Every puzzle gets a number and an optional difficulty. Two puzzles are printed on a page.
+/// The solutions can follow at the end. The document needs the package `tikz`.
+/// ```
+/// use sudoku::sudokus::{Booklet, Sudoku};
+///
+/// let path = "test-sudokus/text/welt-23-11-11-hard-unsolved";
+/// let mut booklet = Booklet::new("Weekly Sudokus", true);
+/// booklet.add(Sudoku::from_file(path), Booklet::difficulty(path));
+///
+/// let latex = booklet.to_latex();
+/// assert!(latex.contains("Puzzle 1"));
+/// assert!(latex.contains("Hard"));
+/// assert!(latex.contains("Solution 1"));
+/// ```
+pub struct Booklet {
+    title: String,
+    solutions: bool,
+    puzzles: Vec<Puzzle>,
+}
+
+impl Booklet {
+    /// Returns an empty booklet. With `solutions` the solutions are printed after the puzzles.
+    pub fn new(title: &str, solutions: bool) -> Self {
+        Self {
+            title: title.to_string(),
+            solutions,
+            puzzles: vec![],
+        }
+    }
+
+    /// Adds a Sudoku as the next puzzle. It is solved for the solution.
+    pub fn add(&mut self, mut sudoku: Sudoku, difficulty: Option<&str>) {
+        let puzzle = sudoku.drawing(false);
+        let solution = match self.solutions {
+            true if sudoku.solve() => Some(sudoku.drawing(false)),
+            _ => None,
+        };
+
+        self.puzzles.push(Puzzle {
+            puzzle,
+            solution,
+            difficulty: difficulty.map(str::to_string),
+        });
+    }
+
+    /// Returns the difficulty out of the name of a Sudoku file like
+    /// `welt-23-11-11-hard-unsolved`, if it has one.
+    /// ```
+    /// use sudoku::sudokus::Booklet;
+    ///
+    /// let path = "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-unsolved";
+    /// assert_eq!(Booklet::difficulty(path), Some("extra-hard"));
+    /// assert_eq!(Booklet::difficulty("test-sudokus/lines/collection-26-10-18-unsolved"), None);
+    /// ```
+    pub fn difficulty(path: &str) -> Option<&'static str> {
+        let name = path.rsplit(['/', '\\']).next()?;
+        let name = name.split('.').next()?;
+
+        DIFFICULTIES.into_iter().find(|difficulty| {
+            name.contains(&format!("-{}-", difficulty))
+                || name.ends_with(&format!("-{}", difficulty))
+        })
+    }
+
+    /// Returns the booklet as LaTeX document
+    pub fn to_latex(&self) -> String {
+        let mut output = String::new();
+        output.push_str("\\documentclass[a4paper]{article}\n");
+        output.push_str("\\usepackage[margin=2cm]{geometry}\n");
+        output.push_str("\\usepackage[T1]{fontenc}\n");
+        output.push_str("\\usepackage{lmodern}\n");
+        output.push_str("\\usepackage{tikz}\n");
+        output.push_str("\\setlength{\\parindent}{0pt}\n");
+        output.push_str("\\begin{document}\n");
+        output.push_str(&format!(
+            "\\begin{{center}}{{\\Huge {}}}\\end{{center}}\n",
+            escape_latex(&self.title)
+        ));
+
+        for (index, puzzle) in self.puzzles.iter().enumerate() {
+            if index != 0 {
+                output.push_str(match index % 2 {
+                    0 => "\\newpage\n",
+                    _ => "\\vfill\n",
+                });
+            }
+
+            output.push_str(&heading(
+                "Puzzle",
+                index,
+                puzzle.difficulty.as_deref(),
+                "\\Large",
+            ));
+            output.push_str(&picture(&puzzle.puzzle, PUZZLE_WIDTH));
+        }
+
+        if self.solutions {
+            output.push_str("\\newpage\n\\section*{Solutions}\n");
+
+            for (index, puzzle) in self.puzzles.iter().enumerate() {
+                // Two solutions are next to each other
+                output.push_str("\\begin{minipage}[t]{0.48\\textwidth}\n");
+                output.push_str(&heading(
+                    "Solution",
+                    index,
+                    puzzle.difficulty.as_deref(),
+                    "\\large",
+                ));
+                match &puzzle.solution {
+                    Some(solution) => output.push_str(&picture(solution, SOLUTION_WIDTH)),
+                    None => output.push_str("The Sudoku couldn't be solved.\n"),
+                }
+                output.push_str("\\end{minipage}\n");
+                output.push_str(match index % 2 {
+                    0 => "\\hfill\n",
+                    _ => "\\par\\bigskip\n",
+                });
+            }
+        }
+
+        output.push_str("\\end{document}\n");
+        output
+    }
+}
+
+/// Returns the heading of a puzzle with its number and difficulty
+fn heading(name: &str, index: usize, difficulty: Option<&str>, size: &str) -> String {
+    let mut output = format!("{{{} {} {}}}", size, name, index + 1);
+    if let Some(difficulty) = difficulty {
+        let mut chars = difficulty.chars();
+        let first = chars
+            .next()
+            .map(|c| c.to_uppercase().to_string())
+            .unwrap_or_default();
+        let difficulty = escape_latex(&(first + &chars.as_str().replace('-', " ")));
+        output.push_str(&format!("\\hfill{{{} {}}}", size, difficulty));
+    }
+    output.push_str("\\par\n");
+    output
+}
+
+/// Returns the drawing centered in the given width
+fn picture(drawing: &Drawing, width: f64) -> String {
+    let cell = width / (drawing.geometry().size() + 2) as f64;
+    format!(
+        "\\begin{{center}}\n{}\\end{{center}}\n",
+        drawing.to_tikz(cell)
+    )
+}
+
+#[cfg(test)]
+mod booklet_test {
+    use super::Booklet;
+    use crate::sudokus::Sudoku;
+
+    #[test]
+    fn latex() {
+        let mut booklet = Booklet::new("Sudokus & more", true);
+        for path in [
+            "test-sudokus/text/constructed-26-10-18-killer-hard-unsolved",
+            "test-sudokus/text/constructed-26-10-18-4x4-easy-unsolved",
+            "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-unsolved",
+        ] {
+            booklet.add(Sudoku::from_file(path), Booklet::difficulty(path));
+        }
+        let latex = booklet.to_latex();
+
+        assert!(latex.starts_with("\\documentclass"));
+        assert!(latex.ends_with("\\end{document}\n"));
+        assert!(latex.contains("{\\Huge Sudokus \\& more}"));
+        assert!(latex.contains("{\\Large Puzzle 1}\\hfill{\\Large Hard}\\par"));
+        assert!(latex.contains("{\\Large Puzzle 3}\\hfill{\\Large Extra hard}\\par"));
+        assert!(latex.contains("{\\large Solution 2}\\hfill{\\large Easy}\\par"));
+        assert_eq!(latex.matches("\\begin{tikzpicture}").count(), 6);
+        // The third puzzle is on the second page
+        assert_eq!(latex.matches("\\newpage").count(), 2);
+
+        let mut booklet = Booklet::new("Sudokus", false);
+        booklet.add(
+            Sudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved"),
+            None,
+        );
+        let latex = booklet.to_latex();
+        assert!(latex.contains("{\\Large Puzzle 1}\\par"));
+        assert!(!latex.contains("Solution"));
+    }
+}
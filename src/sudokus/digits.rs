@@ -0,0 +1,514 @@
+/// Width of a digit after it is scaled
+pub(crate) const WIDTH: usize = 10;
+
+/// Height of a digit after it is scaled
+pub(crate) const HEIGHT: usize = 14;
+
+/// A digit scaled to `HEIGHT` rows and centered, as the part of dark pixels in every cell
+pub(crate) type Glyph = [f64; WIDTH * HEIGHT];
+
+/// The digits in the fonts of the pictures in `test-sudokus/pics`, a digit can have many fonts.
+/// Dark pixels are `#`.
+const TEMPLATES: [(i32, [&str; HEIGHT]); 24] = [
+    (
+        1,
+        [
+            "......#...",
+            "......#...",
+            ".....##...",
+            "...####...",
+            "......#...",
+            "......#...",
+            "......#...",
+            "......#...",
+            "......#...",
+            "......#...",
+            "......#...",
+            "......#...",
+            "......#...",
+            "......#...",
+        ],
+    ),
+    (
+        1,
+        [
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+            "....##....",
+        ],
+    ),
+    (
+        1,
+        [
+            "...####...",
+            "..#####...",
+            "#######...",
+            "##..###...",
+            "....###...",
+            "....###...",
+            "....###...",
+            "....###...",
+            "....###...",
+            "....###...",
+            "....###...",
+            "....###...",
+            "##########",
+            "##########",
+        ],
+    ),
+    (
+        2,
+        [
+            "..####....",
+            ".######...",
+            "##....##..",
+            "##....##..",
+            "......##..",
+            ".....###..",
+            ".....##...",
+            "....###...",
+            "....##....",
+            "...###....",
+            "...##.....",
+            "..###.....",
+            ".#########",
+            ".#########",
+        ],
+    ),
+    (
+        2,
+        [
+            "...####...",
+            "..#######.",
+            ".##....##.",
+            ".##.....#.",
+            ".#......##",
+            ".......##.",
+            ".......##.",
+            ".....###..",
+            "....##....",
+            "..###.....",
+            ".##.......",
+            ".##.......",
+            ".########.",
+            "##########",
+        ],
+    ),
+    (
+        2,
+        [
+            "..######..",
+            ".########.",
+            ".#########",
+            "###....###",
+            ".......###",
+            "......###.",
+            ".....####.",
+            "....####..",
+            "...####...",
+            "..###.....",
+            ".###......",
+            "#########.",
+            "##########",
+            "##########",
+        ],
+    ),
+    (
+        3,
+        [
+            "...####...",
+            "..######..",
+            ".##....##.",
+            ".#.....##.",
+            ".......##.",
+            "......###.",
+            "....####..",
+            "......###.",
+            ".......##.",
+            "........##",
+            ".#......#.",
+            ".##....##.",
+            ".#######..",
+            "...####...",
+        ],
+    ),
+    (
+        3,
+        [
+            "....#####.",
+            "..########",
+            "..##....##",
+            "........##",
+            ".......##.",
+            ".....###..",
+            "...#####..",
+            "...######.",
+            "...#...###",
+            "........##",
+            "........##",
+            ".......###",
+            "#########.",
+            "########..",
+        ],
+    ),
+    (
+        3,
+        [
+            "..######..",
+            ".########.",
+            "##########",
+            "###....###",
+            "......####",
+            "....#####.",
+            "...#####..",
+            "....#####.",
+            "......####",
+            ".......###",
+            "###....###",
+            "##########",
+            ".########.",
+            "..######..",
+        ],
+    ),
+    (
+        4,
+        [
+            "......##..",
+            ".....###..",
+            ".....###..",
+            "....####..",
+            "...##.##..",
+            "...#..##..",
+            "..##..##..",
+            ".##...##..",
+            ".#....##..",
+            "##########",
+            "##########",
+            "......##..",
+            "......##..",
+            "......##..",
+        ],
+    ),
+    (
+        4,
+        [
+            ".......##.",
+            ".......##.",
+            "##.....##.",
+            "##.....##.",
+            "##.....##.",
+            "###....##.",
+            ".##....##.",
+            ".#########",
+            "..########",
+            ".......##.",
+            ".......##.",
+            ".......##.",
+            ".......##.",
+            ".......##.",
+        ],
+    ),
+    (
+        4,
+        [
+            "......#...",
+            ".....##...",
+            ".....##...",
+            "....###...",
+            "...##.#...",
+            "...#..#...",
+            "..#...#...",
+            ".#....#...",
+            "##....#...",
+            "##########",
+            ".########.",
+            "......#...",
+            "......#...",
+            "......#...",
+        ],
+    ),
+    (
+        5,
+        [
+            "..#######.",
+            ".########.",
+            ".##.......",
+            ".##.......",
+            ".##.......",
+            ".######...",
+            ".#######..",
+            ".#.....##.",
+            "........#.",
+            "........##",
+            "........#.",
+            ".#.....##.",
+            ".#######..",
+            "...####...",
+        ],
+    ),
+    (
+        5,
+        [
+            "#########.",
+            "#########.",
+            "####......",
+            "####......",
+            "#######...",
+            "#########.",
+            "##########",
+            "###...####",
+            ".......###",
+            ".......###",
+            "###....###",
+            "##########",
+            ".########.",
+            "..######..",
+        ],
+    ),
+    (
+        6,
+        [
+            "..#.......",
+            ".##.......",
+            ".##.......",
+            "##........",
+            "##........",
+            "##........",
+            "#.......##",
+            "#.....####",
+            "#....#####",
+            "#....##..#",
+            "##..##...#",
+            "##..###.##",
+            "#########.",
+            "..######..",
+        ],
+    ),
+    (
+        6,
+        [
+            "....###...",
+            "..######..",
+            ".##....##.",
+            ".##.......",
+            ".#........",
+            "##..###...",
+            "####..###.",
+            "###....##.",
+            "##......#.",
+            ".#......##",
+            ".#......#.",
+            ".##....##.",
+            "..######..",
+            "...####...",
+        ],
+    ),
+    (
+        6,
+        [
+            "...#####..",
+            "..#######.",
+            ".########.",
+            "###....##.",
+            "###.......",
+            "###.####..",
+            "#########.",
+            "####..####",
+            "###....###",
+            "###....###",
+            "###....###",
+            ".########.",
+            ".########.",
+            "...#####..",
+        ],
+    ),
+    (
+        7,
+        [
+            "##########",
+            ".#########",
+            ".......##.",
+            ".......##.",
+            "......##..",
+            "......#...",
+            ".....##...",
+            ".....#....",
+            "....##....",
+            "....##....",
+            "...##.....",
+            "...##.....",
+            "...##.....",
+            "..##......",
+        ],
+    ),
+    (
+        8,
+        [
+            "...####...",
+            "..######..",
+            ".##....##.",
+            ".##....##.",
+            ".##....##.",
+            "..##..##..",
+            "..######..",
+            ".###..###.",
+            ".##....##.",
+            "##......##",
+            "##......##",
+            ".##....##.",
+            ".########.",
+            "...####...",
+        ],
+    ),
+    (
+        8,
+        [
+            "....####..",
+            "..#######.",
+            ".###...###",
+            ".##....##.",
+            ".###.###..",
+            "..#####...",
+            "..####....",
+            "..####....",
+            ".##.###...",
+            ".##..##...",
+            "###..##...",
+            "###..##...",
+            ".######...",
+            ".#####....",
+        ],
+    ),
+    (
+        8,
+        [
+            "..######..",
+            ".########.",
+            "####..####",
+            "###....###",
+            ".###...###",
+            ".########.",
+            "..######..",
+            ".########.",
+            "###....###",
+            "###....###",
+            "###....###",
+            "####..####",
+            ".########.",
+            "..######..",
+        ],
+    ),
+    (
+        9,
+        [
+            "...####...",
+            ".########.",
+            "###....##.",
+            "##......#.",
+            "###.....##",
+            ".#########",
+            "..########",
+            "........##",
+            "........##",
+            "........##",
+            "........##",
+            "........##",
+            ".......###",
+            "........##",
+        ],
+    ),
+    (
+        9,
+        [
+            "...####...",
+            "..######..",
+            ".##....##.",
+            ".#.....##.",
+            "##......#.",
+            ".#.....###",
+            ".##....###",
+            "..########",
+            "...###..#.",
+            ".......##.",
+            ".......##.",
+            ".##....##.",
+            "..######..",
+            "...###....",
+        ],
+    ),
+    (
+        9,
+        [
+            "..#####...",
+            ".########.",
+            "#########.",
+            "###....###",
+            "###....###",
+            "###....###",
+            "####..####",
+            ".#########",
+            "..####.###",
+            ".......###",
+            ".##....###",
+            ".########.",
+            ".#######..",
+            "..#####...",
+        ],
+    ),
+];
+
+/// Returns the digit that looks most like the glyph
+pub(crate) fn recognize(glyph: &Glyph) -> i32 {
+    TEMPLATES
+        .iter()
+        .map(|(digit, template)| (*digit, distance(glyph, template)))
+        .min_by(|(_, first), (_, second)| first.total_cmp(second))
+        .map_or(0, |(digit, _)| digit)
+}
+
+/// Returns the sum of the squared differences of every pixel
+fn distance(glyph: &Glyph, template: &[&str; HEIGHT]) -> f64 {
+    template
+        .iter()
+        .flat_map(|row| row.chars())
+        .zip(glyph)
+        .map(|(pixel, value)| {
+            let difference = value - if pixel == '#' { 1.0 } else { 0.0 };
+            difference * difference
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod digits_test {
+    use super::{recognize, Glyph, TEMPLATES};
+
+    #[test]
+    fn templates() {
+        for (digit, template) in TEMPLATES {
+            let mut glyph: Glyph = [0.0; super::WIDTH * super::HEIGHT];
+            for (value, pixel) in glyph
+                .iter_mut()
+                .zip(template.iter().flat_map(|row| row.chars()))
+            {
+                *value = if pixel == '#' { 1.0 } else { 0.0 };
+            }
+            assert_eq!(recognize(&glyph), digit);
+        }
+
+        assert!(TEMPLATES
+            .iter()
+            .all(|(_, template)| template.iter().all(|row| row.len() == super::WIDTH)));
+    }
+}
@@ -0,0 +1,80 @@
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{houses_comply, marker_section, SudokuRule};
+
+/// Disjoint Groups Sudoku
+///
+/// The cells at the same position of every box form a group, that has to contain every number once.
+/// It is enabled with the marker `disjoint-groups;`
+#[derive(Debug, Clone)]
+pub struct DisjointGroupsSudoku {
+    groups: Vec<Vec<(usize, usize)>>,
+}
+
+impl SudokuRule for DisjointGroupsSudoku {
+    fn str_identifier() -> &'static str {
+        "disjoint-groups"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        marker_section(input, Self::str_identifier())?;
+        let boxes: Vec<Vec<_>> = (0..geometry.size())
+            .map(|index| geometry.box_cells(index).collect())
+            .collect();
+        let groups = (0..geometry.size())
+            .map(|position| boxes.iter().map(|cells| cells[position]).collect())
+            .collect();
+
+        Ok(Box::new(Self { groups }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        houses_comply(field, &self.groups)
+    }
+
+    fn houses(&self, _size: usize) -> Vec<Vec<(usize, usize)>> {
+        self.groups.clone()
+    }
+}
+
+#[cfg(test)]
+mod disjoint_groups_sudoku_test {
+    use super::DisjointGroupsSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn groups() {
+        let groups = DisjointGroupsSudoku::from_str("disjoint-groups", Geometry::CLASSIC).houses(9);
+
+        assert_eq!(groups.len(), 9);
+        assert_eq!(
+            groups[0],
+            vec![
+                (0, 0),
+                (3, 0),
+                (6, 0),
+                (0, 3),
+                (3, 3),
+                (6, 3),
+                (0, 6),
+                (3, 6),
+                (6, 6)
+            ]
+        );
+        assert!(groups[8].contains(&(8, 8)) && groups[8].contains(&(2, 5)));
+    }
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[1][1] = 0b1;
+        field[4][2] = 0b1;
+
+        let disjoint_groups = DisjointGroupsSudoku::from_str("disjoint-groups", Geometry::CLASSIC);
+        assert!(disjoint_groups.complys(&field));
+
+        field[7][4] = 0b1;
+        assert!(!disjoint_groups.complys(&field));
+    }
+}
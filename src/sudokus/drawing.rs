@@ -0,0 +1,580 @@
+use super::geometry::Geometry;
+
+/// Color of the given numbers
+const GIVEN_COLOR: &str = "#000000";
+
+/// Color of the solved numbers
+const SOLVED_COLOR: &str = "#1a56c4";
+
+/// Color of the candidates
+const CANDIDATE_COLOR: &str = "#707070";
+
+/// Points of LaTeX in a centimeter
+const POINTS_PER_CM: f64 = 28.45;
+
+/// A shape of a drawing. Positions and sizes are in cells and colors like `#a0a0a0` or `none`.
+#[derive(Debug, Clone)]
+enum Shape {
+    Rect {
+        position: (f64, f64),
+        size: (f64, f64),
+        fill: String,
+        stroke: String,
+    },
+    Circle {
+        center: (f64, f64),
+        radius: f64,
+        fill: String,
+        stroke: String,
+    },
+    Line {
+        points: Vec<(f64, f64)>,
+        color: String,
+        width: f64,
+        dashed: bool,
+    },
+    Text {
+        position: (f64, f64),
+        text: String,
+        size: f64,
+        color: String,
+    },
+}
+
+/// A drawing of a Sudoku, that can be written as SVG or as TikZ picture for LaTeX.
+///
+/// All positions are in cells: `(x, y)` is `x` cells right and `y` cells below the top left
+/// corner of the field. A cell `(row, collum)` that indexes the field is the square from
+/// `(row, collum)` to `(row + 1, collum + 1)`, so `x` goes along the lines of the `field` section.
+/// There is a margin of one cell around the field for clues outside of it.
+///
+/// Rules draw their decorations with `SudokuRule::draw`, before the grid and the numbers are drawn.
+pub struct Drawing {
+    geometry: Geometry,
+    shapes: Vec<Shape>,
+}
+
+impl Drawing {
+    /// Size of a cell in pixels in a SVG
+    pub const CELL_SIZE: usize = 50;
+
+    /// Returns an empty drawing for a Sudoku
+    pub fn new(geometry: Geometry) -> Self {
+        Self {
+            geometry,
+            shapes: vec![],
+        }
+    }
+
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
+    }
+
+    /// Returns the center of a cell
+    pub fn center((row, collum): (usize, usize)) -> (f64, f64) {
+        (row as f64 + 0.5, collum as f64 + 0.5)
+    }
+
+    /// Fills a cell with a color
+    pub fn shade(&mut self, (row, collum): (usize, usize), color: &str) {
+        self.rect((row as f64, collum as f64), (1.0, 1.0), color, "none");
+    }
+
+    /// Draws a rectangle. `fill` and `stroke` can be `none`
+    pub fn rect(&mut self, position: (f64, f64), size: (f64, f64), fill: &str, stroke: &str) {
+        self.shapes.push(Shape::Rect {
+            position,
+            size,
+            fill: fill.to_string(),
+            stroke: stroke.to_string(),
+        });
+    }
+
+    /// Draws a circle. `fill` and `stroke` can be `none`
+    pub fn circle(&mut self, center: (f64, f64), radius: f64, fill: &str, stroke: &str) {
+        self.shapes.push(Shape::Circle {
+            center,
+            radius,
+            fill: fill.to_string(),
+            stroke: stroke.to_string(),
+        });
+    }
+
+    /// Draws a line through all points with round ends
+    pub fn line(&mut self, points: &[(f64, f64)], color: &str, width: f64) {
+        self.shapes.push(Shape::Line {
+            points: points.to_vec(),
+            color: color.to_string(),
+            width,
+            dashed: false,
+        });
+    }
+
+    /// Draws a line through all points with a head at the last point
+    pub fn arrow(&mut self, points: &[(f64, f64)], color: &str, width: f64) {
+        self.line(points, color, width);
+
+        let [.., (before_x, before_y), (x, y)] = points else {
+            return;
+        };
+        let length = (x - before_x).hypot(y - before_y);
+        let (dx, dy) = (
+            (x - before_x) / length * 0.12,
+            (y - before_y) / length * 0.12,
+        );
+        self.line(
+            &[
+                (x - dx - dy, y - dy + dx),
+                (*x, *y),
+                (x - dx + dy, y - dy - dx),
+            ],
+            color,
+            width,
+        );
+    }
+
+    /// Draws a line through the centers of the cells
+    pub fn cell_line(&mut self, cells: &[(usize, usize)], color: &str, width: f64) {
+        let points: Vec<_> = cells.iter().map(|&cell| Self::center(cell)).collect();
+        self.line(&points, color, width);
+    }
+
+    /// Draws a text centered on the position. The size is the height of the font in cells.
+    pub fn text(&mut self, position: (f64, f64), text: &str, size: f64, color: &str) {
+        self.shapes.push(Shape::Text {
+            position,
+            text: text.to_string(),
+            size,
+            color: color.to_string(),
+        });
+    }
+
+    /// Draws a dashed line just inside the border of a group of cells, like a killer cage.
+    /// The label is written in the top left corner of the cage.
+    pub fn cage(&mut self, cells: &[(usize, usize)], label: Option<&str>) {
+        const INSET: f64 = 0.08;
+        let contains = |row: isize, collum: isize| {
+            row >= 0 && collum >= 0 && cells.contains(&(row as usize, collum as usize))
+        };
+
+        for &(row, collum) in cells {
+            let (x, y) = Self::center((row, collum));
+            let (r, c) = (row as isize, collum as isize);
+
+            // Every side of the cell with its direction to the outside and along the side
+            for ((nx, ny), (tx, ty)) in [
+                ((0, -1), (1, 0)),
+                ((1, 0), (0, 1)),
+                ((0, 1), (-1, 0)),
+                ((-1, 0), (0, -1)),
+            ] {
+                if contains(r + nx, c + ny) {
+                    continue;
+                }
+
+                // The side ends inside of the cell, at the border or in the next cell of the cage
+                let end = |direction: isize| {
+                    if !contains(r + direction * tx, c + direction * ty) {
+                        0.5 - INSET
+                    } else if !contains(r + direction * tx + nx, c + direction * ty + ny) {
+                        0.5
+                    } else {
+                        0.5 + INSET
+                    }
+                };
+                let side = 0.5 - INSET;
+                let (start, stop) = (end(-1), end(1));
+                self.shapes.push(Shape::Line {
+                    points: vec![
+                        (
+                            x + nx as f64 * side - tx as f64 * start,
+                            y + ny as f64 * side - ty as f64 * start,
+                        ),
+                        (
+                            x + nx as f64 * side + tx as f64 * stop,
+                            y + ny as f64 * side + ty as f64 * stop,
+                        ),
+                    ],
+                    color: "#000000".to_string(),
+                    width: 0.025,
+                    dashed: true,
+                });
+            }
+        }
+
+        if let (Some(label), Some(&(row, collum))) = (
+            label,
+            cells.iter().min_by_key(|&&(row, collum)| (collum, row)),
+        ) {
+            let width = 0.13 * label.chars().count() as f64 + 0.04;
+            self.rect(
+                (row as f64 + 0.03, collum as f64 + 0.03),
+                (width, 0.24),
+                "#ffffff",
+                "none",
+            );
+            self.text(
+                (row as f64 + 0.05 + width / 2.0 - 0.02, collum as f64 + 0.15),
+                label,
+                0.22,
+                "#000000",
+            );
+        }
+    }
+
+    /// Draws the lines between the cells and the boxes
+    pub(crate) fn grid(&mut self) {
+        let size = self.geometry.size();
+        for i in 0..=size {
+            let box_row = i % self.geometry.box_width() == 0;
+            let box_collum = i % self.geometry.box_height() == 0;
+            let width = |thick| if thick { 0.06 } else { 0.02 };
+
+            self.line(
+                &[(i as f64, 0.0), (i as f64, size as f64)],
+                "#000000",
+                width(box_row),
+            );
+            self.line(
+                &[(0.0, i as f64), (size as f64, i as f64)],
+                "#000000",
+                width(box_collum),
+            );
+        }
+    }
+
+    /// Draws the number of a cell. Given numbers have a different color than solved ones.
+    pub(crate) fn number(&mut self, cell: (usize, usize), number: i32, given: bool) {
+        let color = if given { GIVEN_COLOR } else { SOLVED_COLOR };
+        let text = Geometry::number_to_char(number).to_string();
+        self.text(Self::center(cell), &text, 0.6, color);
+    }
+
+    /// Draws the candidates of a cell in the raw format. They are placed like the cells of a box.
+    pub(crate) fn candidates(&mut self, (row, collum): (usize, usize), candidates: u32) {
+        let (width, height) = (self.geometry.box_width(), self.geometry.box_height());
+        let size = 0.7 / width.max(height) as f64;
+
+        for number in 1..=self.geometry.size() {
+            if candidates & (0b1 << (number - 1)) == 0 {
+                continue;
+            }
+
+            let x = row as f64 + ((number - 1) % width) as f64 / width as f64 + 0.5 / width as f64;
+            let y =
+                collum as f64 + ((number - 1) / width) as f64 / height as f64 + 0.5 / height as f64;
+            let text = Geometry::number_to_char(number as i32).to_string();
+            self.text((x, y), &text, size, CANDIDATE_COLOR);
+        }
+    }
+
+    /// Returns the drawing as SVG document
+    pub fn to_svg(&self) -> String {
+        let cells = self.geometry.size() + 2;
+        let mut output = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="-1 -1 {} {}" font-family="sans-serif">"#,
+            cells * Self::CELL_SIZE,
+            cells * Self::CELL_SIZE,
+            cells,
+            cells
+        );
+        output.push('\n');
+        output.push_str(&format!(
+            r##"<rect x="-1" y="-1" width="{}" height="{}" fill="#ffffff"/>"##,
+            cells, cells
+        ));
+        output.push('\n');
+
+        for shape in &self.shapes {
+            let element = match shape {
+                Shape::Rect {
+                    position: (x, y),
+                    size: (width, height),
+                    fill,
+                    stroke,
+                } => format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="{}" stroke-width="0.03"/>"#,
+                    num(*x),
+                    num(*y),
+                    num(*width),
+                    num(*height),
+                    fill,
+                    stroke
+                ),
+                Shape::Circle {
+                    center: (x, y),
+                    radius,
+                    fill,
+                    stroke,
+                } => format!(
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="{}" stroke-width="0.04"/>"#,
+                    num(*x),
+                    num(*y),
+                    num(*radius),
+                    fill,
+                    stroke
+                ),
+                Shape::Line {
+                    points,
+                    color,
+                    width,
+                    dashed,
+                } => {
+                    let points: Vec<_> = points
+                        .iter()
+                        .map(|&(x, y)| format!("{},{}", num(x), num(y)))
+                        .collect();
+                    let style = match dashed {
+                        true => r#"stroke-dasharray="0.08 0.06""#,
+                        false => r#"stroke-linecap="round" stroke-linejoin="round""#,
+                    };
+                    format!(
+                        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" {}/>"#,
+                        points.join(" "),
+                        color,
+                        num(*width),
+                        style
+                    )
+                }
+                Shape::Text {
+                    position: (x, y),
+                    text,
+                    size,
+                    color,
+                } => format!(
+                    r#"<text x="{}" y="{}" font-size="{}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    num(*x),
+                    num(*y),
+                    num(*size),
+                    color,
+                    escape_xml(text)
+                ),
+            };
+            output.push_str(&element);
+            output.push('\n');
+        }
+        output.push_str("</svg>\n");
+        output
+    }
+
+    /// Returns the drawing as TikZ picture. A cell is `cell` centimeters wide.
+    ///
+    /// The picture needs `\usepackage{tikz}` in the LaTeX document.
+    pub fn to_tikz(&self, cell: f64) -> String {
+        let cells = self.geometry.size() + 1;
+        let mut output = format!(
+            "\\begin{{tikzpicture}}[x={}cm, y=-{}cm]\n",
+            num(cell),
+            num(cell)
+        );
+        // The margin is part of the picture, so all pictures of a size have the same size
+        output.push_str(&format!(
+            "\\path (-1,-1) rectangle ({},{});\n",
+            cells, cells
+        ));
+
+        // Widths are in cells like the positions
+        let width = |width: f64| format!("line width={}cm", num(width * cell));
+        let paint = |fill: &str, stroke: &str, line: f64| {
+            let mut options = vec![];
+            if fill != "none" {
+                options.push(format!("fill={}", tikz_color(fill)));
+            }
+            if stroke != "none" {
+                options.push(format!("draw={}", tikz_color(stroke)));
+                options.push(width(line));
+            }
+            options.join(", ")
+        };
+
+        for shape in &self.shapes {
+            let command = match shape {
+                Shape::Rect {
+                    position: (x, y),
+                    size: (w, h),
+                    fill,
+                    stroke,
+                } => format!(
+                    "\\path[{}] ({},{}) rectangle ({},{});",
+                    paint(fill, stroke, 0.03),
+                    num(*x),
+                    num(*y),
+                    num(x + w),
+                    num(y + h)
+                ),
+                Shape::Circle {
+                    center: (x, y),
+                    radius,
+                    fill,
+                    stroke,
+                } => format!(
+                    "\\path[{}] ({},{}) circle[radius={}];",
+                    paint(fill, stroke, 0.04),
+                    num(*x),
+                    num(*y),
+                    num(*radius)
+                ),
+                Shape::Line {
+                    points,
+                    color,
+                    width: line,
+                    dashed,
+                } => {
+                    let points: Vec<_> = points
+                        .iter()
+                        .map(|&(x, y)| format!("({},{})", num(x), num(y)))
+                        .collect();
+                    let style = match dashed {
+                        true => format!(
+                            "dash pattern=on {}cm off {}cm",
+                            num(0.08 * cell),
+                            num(0.06 * cell)
+                        ),
+                        false => "line cap=round, line join=round".to_string(),
+                    };
+                    format!(
+                        "\\path[draw={}, {}, {}] {};",
+                        tikz_color(color),
+                        width(*line),
+                        style,
+                        points.join(" -- ")
+                    )
+                }
+                Shape::Text {
+                    position: (x, y),
+                    text,
+                    size,
+                    color,
+                } => {
+                    let points = num(size * cell * POINTS_PER_CM);
+                    format!(
+                        "\\node[text={}, inner sep=0, font=\\sffamily\\fontsize{{{}}}{{{}}}\\selectfont] at ({},{}) {{{}}};",
+                        tikz_color(color),
+                        points,
+                        points,
+                        num(*x),
+                        num(*y),
+                        escape_latex(text)
+                    )
+                }
+            };
+            output.push_str(&command);
+            output.push('\n');
+        }
+        output.push_str("\\end{tikzpicture}\n");
+        output
+    }
+}
+
+/// Writes a coordinate without unnecessary digits
+fn num(value: f64) -> String {
+    format!("{}", (value * 1000.0).round() / 1000.0)
+}
+
+/// Escapes the characters of a text that have a meaning in XML
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escapes the characters of a text that have a meaning in LaTeX
+pub(crate) fn escape_latex(text: &str) -> String {
+    let mut output = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => output.push_str("\\textbackslash{}"),
+            '<' => output.push_str("\\textless{}"),
+            '>' => output.push_str("\\textgreater{}"),
+            '~' => output.push_str("\\textasciitilde{}"),
+            '^' => output.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+/// Returns a color like `#a0a0a0` in the format of TikZ
+fn tikz_color(color: &str) -> String {
+    let channel = |index: usize| {
+        color
+            .get(index..index + 2)
+            .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+            .unwrap_or(0)
+    };
+    format!(
+        "{{rgb,255:red,{};green,{};blue,{}}}",
+        channel(1),
+        channel(3),
+        channel(5)
+    )
+}
+
+#[cfg(test)]
+mod drawing_test {
+    use super::{escape_latex, Drawing};
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn cage() {
+        let mut drawing = Drawing::new(Geometry::CLASSIC);
+        drawing.cage(&[(0, 0), (1, 0), (1, 1)], Some("<10"));
+        let output = drawing.to_svg();
+
+        // Every side of a cell on the border of the cage is drawn
+        assert_eq!(output.matches("stroke-dasharray").count(), 8);
+        assert!(output.contains(r#"<polyline points="0.08,0.08 1,0.08""#));
+        // The sides meet in the concave corner
+        assert!(output.contains(r#"<polyline points="1.08,0.92 0.08,0.92""#));
+        assert!(output.contains(r#"<polyline points="1.08,1.92 1.08,0.92""#));
+        assert!(output.contains("&lt;10</text>"));
+    }
+
+    #[test]
+    fn svg() {
+        let mut drawing = Drawing::new(Geometry::new(4, 2, 2).unwrap());
+        drawing.grid();
+        drawing.number((0, 0), 3, true);
+        drawing.candidates((1, 0), 0b1001);
+        let output = drawing.to_svg();
+
+        assert!(output.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="300" height="300" viewBox="-1 -1 6 6""#));
+        assert_eq!(output.matches("<polyline").count(), 10);
+        assert!(output.contains(r##"<text x="0.5" y="0.5" font-size="0.6" fill="#000000""##));
+        assert!(output.contains(r#"<text x="1.25" y="0.25" font-size="0.35""#));
+        assert!(output.contains(r#"<text x="1.75" y="0.75" font-size="0.35""#));
+        assert!(output.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn tikz() {
+        let mut drawing = Drawing::new(Geometry::new(4, 2, 2).unwrap());
+        drawing.grid();
+        drawing.number((0, 0), 3, false);
+        drawing.circle((1.5, 0.5), 0.4, "none", "#808080");
+        drawing.cage(&[(2, 2)], Some("<10"));
+        let output = drawing.to_tikz(1.0);
+
+        assert!(output
+            .starts_with("\\begin{tikzpicture}[x=1cm, y=-1cm]\n\\path (-1,-1) rectangle (5,5);\n"));
+        assert!(output.contains("\\path[draw={rgb,255:red,0;green,0;blue,0}, line width=0.06cm, line cap=round, line join=round] (0,0) -- (0,4);"));
+        assert!(output.contains("\\node[text={rgb,255:red,26;green,86;blue,196}, inner sep=0, font=\\sffamily\\fontsize{17.07}{17.07}\\selectfont] at (0.5,0.5) {3};"));
+        assert!(output.contains("\\path[draw={rgb,255:red,128;green,128;blue,128}, line width=0.04cm] (1.5,0.5) circle[radius=0.4];"));
+        assert!(output.contains("dash pattern=on 0.08cm off 0.06cm] (2.08,2.08) -- (2.92,2.08);"));
+        assert!(output.contains("{\\textless{}10};"));
+        assert!(output.ends_with("\\end{tikzpicture}\n"));
+    }
+
+    #[test]
+    fn latex() {
+        assert_eq!(
+            escape_latex("Sudoku #1: 50% & {more}"),
+            "Sudoku \\#1: 50\\% \\& \\{more\\}"
+        );
+        assert_eq!(escape_latex("a\\b"), "a\\textbackslash{}b");
+    }
+}
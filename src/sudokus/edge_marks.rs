@@ -0,0 +1,160 @@
+use super::drawing::Drawing;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{
+    all_numbers, candidates, invalid_section, orthogonal_neighbours, parse_edge, related_numbers,
+};
+
+/// A mark between two orthogonally adjacent cells like a Kropki dot or a XV sign
+pub(crate) trait EdgeMark: Copy + PartialEq + 'static {
+    /// All marks of this kind
+    const ALL: &'static [Self];
+
+    /// Name of the mark in the file section
+    fn name(self) -> &'static str;
+
+    /// Returns true if the numbers of both cells fit the mark
+    fn related(self, first: i32, second: i32) -> bool;
+
+    /// Draws the mark on the middle of the edge
+    fn draw(self, drawing: &mut Drawing, position: (f64, f64));
+}
+
+/// A mark on the edge between the cells first and second
+#[derive(Debug, Clone)]
+struct MarkedEdge<M> {
+    first: (usize, usize),
+    second: (usize, usize),
+    mark: M,
+}
+
+/// Marks on the edges between cells.
+///
+/// Every kind of mark has its own line with the marked edges.
+/// If the line `negative` is set, all marks are given.
+/// So there is no relation between cells without a mark:
+/// ```text
+/// white: r1c1-r1c2 r4c4-r5c4
+/// black: r2c2-r2c3
+/// negative
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct EdgeMarks<M> {
+    marks: Vec<MarkedEdge<M>>,
+    negative: bool,
+}
+
+impl<M: EdgeMark> EdgeMarks<M> {
+    /// Reads the marks of the content of the section with the identifier
+    pub(crate) fn from_str(input: &str, identifier: &str, size: usize) -> Result<Self, ParseError> {
+        let mut edge_marks = Self {
+            marks: vec![],
+            negative: false,
+        };
+
+        for line in input.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line == "negative" {
+                edge_marks.negative = true;
+                continue;
+            }
+
+            let (name, edges) = line.split_once(':').ok_or_else(|| {
+                invalid_section(identifier, format!("Edge marks need a name: {}", line))
+            })?;
+            let mark = *M::ALL
+                .iter()
+                .find(|mark| mark.name() == name.trim())
+                .ok_or_else(|| invalid_section(identifier, format!("Unknown edge mark {}", name)))?;
+
+            for edge in edges.split_whitespace() {
+                let (first, second) = parse_edge(edge, size)
+                    .ok_or_else(|| invalid_section(identifier, format!("Invalid edge {}", edge)))?;
+                edge_marks.marks.push(MarkedEdge { first, second, mark });
+            }
+        }
+
+        Ok(edge_marks)
+    }
+
+    /// Returns the mark between two cells
+    fn mark(&self, first: (usize, usize), second: (usize, usize)) -> Option<M> {
+        self.marks
+            .iter()
+            .find(|edge| {
+                (edge.first == first && edge.second == second)
+                    || (edge.first == second && edge.second == first)
+            })
+            .map(|edge| edge.mark)
+    }
+
+    /// Returns true if no set numbers contradict a mark
+    pub(crate) fn complys(&self, field: &[Vec<u32>]) -> bool {
+        let size = field.len();
+        for row in 0..size {
+            for collum in 0..size {
+                if field[row][collum].count_ones() != 1 {
+                    continue;
+                }
+                let number = field[row][collum].trailing_zeros() as i32 + 1;
+
+                // Only look to the right and down, so every edge is checked once
+                for (other_row, other_collum) in [(row + 1, collum), (row, collum + 1)] {
+                    if other_row >= size
+                        || other_collum >= size
+                        || field[other_row][other_collum].count_ones() != 1
+                    {
+                        continue;
+                    }
+                    let other = field[other_row][other_collum].trailing_zeros() as i32 + 1;
+
+                    match self.mark((row, collum), (other_row, other_collum)) {
+                        Some(mark) if !mark.related(number, other) => return false,
+                        None if self.negative
+                            && M::ALL.iter().any(|mark| mark.related(number, other)) =>
+                        {
+                            return false
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns the numbers that are possible with the candidates of the neighbours
+    pub(crate) fn possible_numbers(&self, field: &[Vec<u32>], row: usize, collum: usize) -> u32 {
+        let size = field.len();
+        let mut possible = all_numbers(field);
+
+        for (other_row, other_collum) in orthogonal_neighbours(row, collum, size) {
+            let other = candidates(field, other_row, other_collum);
+
+            match self.mark((row, collum), (other_row, other_collum)) {
+                Some(mark) => possible &= related_numbers(other, size, |a, b| mark.related(a, b)),
+                None if self.negative => {
+                    possible &= related_numbers(other, size, |a, b| {
+                        M::ALL.iter().all(|mark| !mark.related(a, b))
+                    })
+                }
+                None => {}
+            }
+        }
+
+        possible
+    }
+
+    /// Draws all marks on their edges
+    pub(crate) fn draw(&self, drawing: &mut Drawing) {
+        for edge in &self.marks {
+            let (first_x, first_y) = Drawing::center(edge.first);
+            let (second_x, second_y) = Drawing::center(edge.second);
+            edge.mark.draw(drawing, ((first_x + second_x) / 2.0, (first_y + second_y) / 2.0));
+        }
+    }
+}
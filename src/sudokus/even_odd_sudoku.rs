@@ -1,8 +1,24 @@
-use super::sudoku_rule::SudokuRule;
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{section_content, SudokuRule};
 
+/// All odd numbers in the raw format. Bit 0 is the number 1.
+const ODD: u32 = 0x5555_5555;
+
+/// Even-Odd Sudoku
+///
+/// The section has a mark for every cell, in the same order as the `field` section.
+/// `0` is an even cell, `1` an odd cell and `.` a cell that can have any number:
+/// ```text
+/// even-odd-sudoku:
+/// 0 1 .  . . .  1 0 .
+/// ...;
+/// ```
 #[derive(Debug, Clone)]
 pub struct EvenOddSudoku {
-    even_fields: [[bool; 9]; 9],
+    /// Numbers that are not possible in a cell in the raw format. It is indexed like the field.
+    not_possible: Vec<Vec<u32>>,
 }
 
 impl SudokuRule for EvenOddSudoku {
@@ -10,90 +26,178 @@ impl SudokuRule for EvenOddSudoku {
         "even-odd-sudoku"
     }
 
-    fn from_str(_input: &str) -> Box<Self> {
-        let mut even_odd = Self::empty();
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let size = geometry.size();
+        let mut not_possible = vec![vec![0; size]; size];
         let mut count = 0;
-        for c in _input.chars() {
-            if count == 81 {
-                break;
+        for c in section_content(input, Self::str_identifier()).chars() {
+            if c.is_whitespace() {
+                continue;
             }
-            if c == '0' || c == '1' {
-                even_odd.even_fields[count % 9][count / 9] = c.to_digit(10).unwrap_or(0) == 0;
-                count += 1;
+            if count == size * size {
+                return Err(Self::error("There are more marks than cells".to_string()));
             }
-        }
 
-        // ToDo: Return Result and not just panic if their is an error while parsing
-        if even_odd.is_valid(){
-            panic!();
+            not_possible[count % size][count / size] = match c {
+                '0' => geometry.all() & ODD,
+                '1' => geometry.all() & !ODD,
+                '.' => 0,
+                _ => return Err(Self::error(format!("Unknown mark {}", c))),
+            };
+            count += 1;
+        }
+        if count != size * size {
+            return Err(Self::error(format!(
+                "There are {} marks for {} cells",
+                count,
+                size * size
+            )));
         }
-        Box::new(even_odd)
+
+        let even_odd = Self { not_possible };
+        even_odd.check_houses(geometry)?;
+        Ok(Box::new(even_odd))
     }
 
-    fn complys(&self, field: &[[i32; 9]; 9]) -> bool {
-        for row in 0..9 {
-            for column in 0..9 {
-                if field[row][column].count_ones() != 1 {
-                    continue;
-                }
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        field
+            .iter()
+            .flatten()
+            .zip(self.not_possible.iter().flatten())
+            .all(|(&number, &not_possible)| number.count_ones() != 1 || number & not_possible == 0)
+    }
 
-                if field[row][column].trailing_zeros() % 2 == 0 && self.even_fields[row][column] {
-                    return false;
-                }
+    fn get_not_possible_numbers_raw(&self, _field: &[Vec<u32>], row: usize, collum: usize) -> u32 {
+        self.not_possible[row][collum]
+    }
 
-                if field[row][column].trailing_zeros() % 2 != 0 && !self.even_fields[row][column] {
-                    return false;
+    /// Even cells are grey squares and odd cells grey circles
+    fn draw(&self, drawing: &mut Drawing) {
+        for (row, cells) in self.not_possible.iter().enumerate() {
+            for (collum, &not_possible) in cells.iter().enumerate() {
+                match not_possible {
+                    0 => continue,
+                    _ if not_possible & ODD != 0 => {
+                        drawing.rect((row as f64 + 0.15, collum as f64 + 0.15), (0.7, 0.7), "#d0d0d0", "none")
+                    }
+                    _ => drawing.circle(Drawing::center((row, collum)), 0.35, "#d0d0d0", "none"),
                 }
             }
         }
-
-        true
-    }
-
-    fn get_not_possible_numbers_raw(
-        &self,
-        _field: &[[i32; 9]; 9],
-        row: usize,
-        collum: usize,
-    ) -> i32 {
-        if self.even_fields[row][collum] {
-            return 0b101_010_101;
-        }
-
-        0b010_101_010
     }
 }
 
 impl EvenOddSudoku {
-    fn empty() -> Self {
-        Self {
-            even_fields: [[false; 9]; 9],
+    fn error(message: String) -> ParseError {
+        ParseError::InvalidSection {
+            identifier: Self::str_identifier().to_string(),
+            message,
         }
     }
 
-    fn is_valid(&self) -> bool {
-        // Their are 36 even fields in a classic sudoku
-        self.even_fields
-            .map(|r| {
-                r.map(|e| {
-                    if e {
-                        return 0;
-                    }
-                    1
-                })
-                .as_slice().iter().sum::<i32>()
-            })
-            .as_slice().iter().sum::<i32>() == 36
+    /// Returns an error if a row, collum or box has more even or odd cells than there are even or odd numbers
+    fn check_houses(&self, geometry: Geometry) -> Result<(), ParseError> {
+        let size = geometry.size();
+        let even = geometry.all() & !ODD;
+        let odd = geometry.all() & ODD;
+
+        for i in 0..size {
+            let row: Vec<_> = (0..size).map(|j| (i, j)).collect();
+            let collum: Vec<_> = (0..size).map(|j| (j, i)).collect();
+            let cells: Vec<_> = geometry.box_cells(i).collect();
+
+            for house in [row, collum, cells] {
+                // The even numbers are not possible in an odd cell
+                let odd_cells = house
+                    .iter()
+                    .filter(|&&(r, c)| self.not_possible[r][c] == even)
+                    .count();
+                let even_cells = house
+                    .iter()
+                    .filter(|&&(r, c)| self.not_possible[r][c] == odd)
+                    .count();
+
+                if odd_cells > odd.count_ones() as usize || even_cells > even.count_ones() as usize
+                {
+                    return Err(Self::error(format!(
+                        "The row, column or box of r{}c{} has too many even or odd cells",
+                        house[0].1 + 1,
+                        house[0].0 + 1
+                    )));
+                }
+            }
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
-mod x_sudoku_test {
+mod even_odd_sudoku_test {
     use super::EvenOddSudoku;
     use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::{Geometry, ParseError};
+
+    /// The first line of the file is `0 1 . 0 1 . 0 1 .`, all other cells can have any number
+    fn partial() -> String {
+        let mut input = "even-odd-sudoku:\n0 1 . 0 1 . 0 1 .\n".to_string();
+        input.push_str(&". ".repeat(72));
+        input
+    }
 
     #[test]
     fn comply() {
-        // ToDo: Write tests
+        let even_odd = EvenOddSudoku::from_str(&partial(), Geometry::CLASSIC);
+
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b10;
+        field[1][0] = 0b1;
+        field[2][0] = 0b10;
+        field[8][8] = 0b10;
+        assert!(even_odd.complys(&field));
+
+        field[0][0] = 0b100;
+        assert!(!even_odd.complys(&field));
+
+        field[0][0] = 0b10;
+        field[1][0] = 0b1000;
+        assert!(!even_odd.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let even_odd = EvenOddSudoku::from_str(&partial(), Geometry::CLASSIC);
+        let field = vec![vec![0; 9]; 9];
+
+        assert_eq!(
+            even_odd.get_not_possible_numbers_raw(&field, 0, 0),
+            0b101_010_101
+        );
+        assert_eq!(
+            even_odd.get_not_possible_numbers_raw(&field, 1, 0),
+            0b010_101_010
+        );
+        assert_eq!(even_odd.get_not_possible_numbers_raw(&field, 2, 0), 0);
+        assert_eq!(even_odd.get_not_possible_numbers_raw(&field, 4, 4), 0);
+    }
+
+    #[test]
+    fn invalid() {
+        // Too few marks
+        assert!(EvenOddSudoku::try_from_str("even-odd-sudoku:\n0 1 0", Geometry::CLASSIC).is_err());
+
+        // Unknown mark
+        let input = partial().replacen('.', "x", 1);
+        assert!(matches!(
+            EvenOddSudoku::try_from_str(&input, Geometry::CLASSIC),
+            Err(ParseError::InvalidSection { .. })
+        ));
+
+        // There are only 4 even numbers for the first line
+        let input = partial().replacen('.', "0", 2);
+        assert!(EvenOddSudoku::try_from_str(&input, Geometry::CLASSIC).is_err());
+
+        let input = partial().replacen('.', "0", 1);
+        assert!(EvenOddSudoku::try_from_str(&input, Geometry::CLASSIC).is_ok());
     }
 }
@@ -0,0 +1,98 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{houses_comply, invalid_section, parse_cells, section_content, SudokuRule};
+
+/// Extra Regions Sudoku
+///
+/// The shaded regions can't contain a number twice. A region with as many cells
+/// as the size of the Sudoku has to contain every number once, so a Hyper Sudoku
+/// has four regions of 3x3 cells. Every region is written in its own line:
+/// ```text
+/// extra-regions:
+/// r2c2 r2c3 r2c4 r3c2 r3c3 r3c4 r4c2 r4c3 r4c4
+/// r6c6 r6c7 r7c6;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExtraRegionsSudoku {
+    regions: Vec<Vec<(usize, usize)>>,
+}
+
+impl SudokuRule for ExtraRegionsSudoku {
+    fn str_identifier() -> &'static str {
+        "extra-regions"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let mut regions = vec![];
+        for line in section_content(input, Self::str_identifier()).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut cells = parse_cells(line, geometry.size()).ok_or_else(|| {
+                invalid_section(Self::str_identifier(), format!("Invalid cell in {}", line))
+            })?;
+            cells.sort();
+            cells.dedup();
+            if cells.len() > geometry.size() {
+                return Err(invalid_section(
+                    Self::str_identifier(),
+                    "A region can't have more cells than the size of the Sudoku",
+                ));
+            }
+
+            regions.push(cells);
+        }
+
+        Ok(Box::new(Self { regions }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        houses_comply(field, &self.regions)
+    }
+
+    fn houses(&self, _size: usize) -> Vec<Vec<(usize, usize)>> {
+        self.regions.clone()
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        for &cell in self.regions.iter().flatten() {
+            drawing.shade(cell, "#e0e0e0");
+        }
+    }
+}
+
+#[cfg(test)]
+mod extra_regions_sudoku_test {
+    use super::ExtraRegionsSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn parse() {
+        let extra_regions = ExtraRegionsSudoku::from_str(
+            "extra-regions:\nr1c1 r1c2\n\nr9c9 r8c9 r9c9",
+            Geometry::CLASSIC,
+        );
+
+        assert_eq!(
+            extra_regions.houses(9),
+            vec![vec![(0, 0), (1, 0)], vec![(8, 7), (8, 8)]]
+        );
+    }
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b10;
+        field[8][8] = 0b10;
+
+        let extra_regions =
+            ExtraRegionsSudoku::from_str("extra-regions:\nr1c1 r1c2 r9c9", Geometry::CLASSIC);
+        assert!(!extra_regions.complys(&field));
+
+        field[8][8] = 0b100;
+        assert!(extra_regions.complys(&field));
+    }
+}
@@ -0,0 +1,537 @@
+use serde_json::{Map, Value};
+
+use super::geometry::Geometry;
+use super::lz_string::decompress_from_base64;
+use super::parse_error::ParseError;
+
+/// Keys of f-puzzles without a rule, like the title or drawings without a meaning
+const IGNORED: [&str; 9] = [
+    "size",
+    "grid",
+    "title",
+    "author",
+    "ruleset",
+    "solution",
+    "highlightConflicts",
+    "text",
+    "disabledlogic",
+];
+
+/// Converts a puzzle of f-puzzles into the sections of a file.
+///
+/// The input is the JSON of the puzzle, its compressed form or a link to it on
+/// f-puzzles (`https://www.f-puzzles.com/?load=...`) or SudokuPad (`https://sudokupad.app/fpuzzles...`).
+pub(crate) fn to_sections(input: &str) -> Result<String, ParseError> {
+    let json = decode(input)?;
+    let puzzle: Value = serde_json::from_str(&json).map_err(|error| invalid(error.to_string()))?;
+    let puzzle = puzzle
+        .as_object()
+        .ok_or_else(|| invalid("The puzzle is not an object".to_string()))?;
+
+    let size = match puzzle.get("size") {
+        Some(size) => size
+            .as_u64()
+            .ok_or_else(|| invalid("The size is not a number".to_string()))?
+            as usize,
+        None => 9,
+    };
+    let geometry =
+        Geometry::with_size(size).ok_or_else(|| invalid(format!("Unsupported size {}", size)))?;
+
+    let mut sections = vec![];
+    if size != 9 {
+        sections.push(format!("size: {};", size));
+    }
+    sections.push(field(puzzle, geometry)?);
+
+    let mut extra_regions = diagonals(puzzle, size, &mut sections);
+    let mut marks = vec!['.'; size * size];
+    let mut line_rows = vec![];
+    let mut little_killers = vec![];
+    let mut sandwich_rows = vec!["-".to_string(); size];
+    let mut sandwich_columns = vec!["-".to_string(); size];
+    let (mut white, mut black) = (vec![], vec![]);
+    let (mut x, mut v) = (vec![], vec![]);
+    for (key, value) in puzzle {
+        if IGNORED.contains(&key.as_str()) || key.starts_with("diagonal") || is_empty(value) {
+            continue;
+        }
+
+        match key.as_str() {
+            "antiknight" => sections.push("anti-knight;".to_string()),
+            "antiking" => sections.push("anti-king;".to_string()),
+            "nonconsecutive" => sections.push("non-consecutive;".to_string()),
+            "disjointgroups" => sections.push("disjoint-groups;".to_string()),
+            "killercage" => {
+                let mut cages = vec![];
+                for cage in constraints(value, key)? {
+                    let sum = cage_sum(cage.get("value"))?;
+                    cages.push(format!(
+                        "{} {}",
+                        sum,
+                        cells(cage.get("cells"), size)?.join(" ")
+                    ));
+                }
+                sections.push(format!("killer:\n{};", cages.join("\n")));
+            }
+            "thermometer" => {
+                let mut thermos = vec![];
+                for thermo in constraints(value, key)? {
+                    for line in lines(thermo.get("lines"), size)? {
+                        thermos.push(line.join(" "));
+                    }
+                }
+                sections.push(format!("thermo:\n{};", thermos.join("\n")));
+            }
+            "arrow" => {
+                let mut arrows = vec![];
+                for arrow in constraints(value, key)? {
+                    let circle = cells(arrow.get("cells"), size)?;
+                    if circle.len() != 1 {
+                        return Err(unsupported("arrows with more than one cell in the circle"));
+                    }
+
+                    let mut cells = circle.clone();
+                    for cell in lines(arrow.get("lines"), size)?.into_iter().flatten() {
+                        if !cells.contains(&cell) {
+                            cells.push(cell);
+                        }
+                    }
+                    arrows.push(cells.join(" "));
+                }
+                sections.push(format!("arrow:\n{};", arrows.join("\n")));
+            }
+            "even" | "odd" => {
+                let mark = if key == "even" { '0' } else { '1' };
+                for constraint in constraints(value, key)? {
+                    let (row, collum) = position(constraint.get("cell"), size)?;
+                    marks[(row - 1) * size + collum - 1] = mark;
+                }
+            }
+            "extraregion" => {
+                for region in constraints(value, key)? {
+                    extra_regions.push(cells(region.get("cells"), size)?.join(" "));
+                }
+            }
+            "palindrome" => {
+                for palindrome in constraints(value, key)? {
+                    for line in lines(palindrome.get("lines"), size)? {
+                        line_rows.push(format!("palindrome: {}", line.join(" ")));
+                    }
+                }
+            }
+            "littlekillersum" => {
+                for clue in constraints(value, key)? {
+                    let (row, collum) = clue_position(clue.get("cell"), size)?;
+                    let direction = clue
+                        .get("direction")
+                        .and_then(Value::as_str)
+                        .filter(|direction| ["DR", "DL", "UR", "UL"].contains(direction))
+                        .ok_or_else(|| invalid("Invalid little killer direction".to_string()))?;
+                    if let Some(sum) = clue_value(clue.get("value"))? {
+                        little_killers.push(format!(
+                            "r{}c{} {} {}",
+                            row,
+                            collum,
+                            direction.to_lowercase(),
+                            sum
+                        ));
+                    }
+                }
+            }
+            "sandwichsum" => {
+                for clue in constraints(value, key)? {
+                    let (row, collum) = clue_position(clue.get("cell"), size)?;
+                    let Some(sum) = clue_value(clue.get("value"))? else {
+                        continue;
+                    };
+                    let clue = match (row, collum) {
+                        (0, collum) if collum <= size => &mut sandwich_columns[collum - 1],
+                        (row, 0) if row <= size => &mut sandwich_rows[row - 1],
+                        _ => return Err(unsupported("sandwich clues right of or below the grid")),
+                    };
+                    *clue = sum.to_string();
+                }
+            }
+            "difference" | "ratio" => {
+                let (default, edges) = match key.as_str() {
+                    "difference" => (1, &mut white),
+                    _ => (2, &mut black),
+                };
+                for dot in constraints(value, key)? {
+                    if clue_value(dot.get("value"))?.is_some_and(|value| value != default) {
+                        return Err(unsupported(&format!("{} dots with other values", key)));
+                    }
+                    edges.push(edge(dot.get("cells"), size)?);
+                }
+            }
+            "xv" => {
+                for sign in constraints(value, key)? {
+                    let edge = edge(sign.get("cells"), size)?;
+                    match sign.get("value").and_then(Value::as_str) {
+                        Some("X") => x.push(edge),
+                        Some("V") => v.push(edge),
+                        _ => return Err(invalid("A XV sign needs the value X or V".to_string())),
+                    }
+                }
+            }
+            _ => return Err(unsupported(key)),
+        }
+    }
+
+    if marks.iter().any(|&mark| mark != '.') {
+        let lines: Vec<String> = marks
+            .chunks(size)
+            .map(|line| {
+                line.iter()
+                    .map(char::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        sections.push(format!("even-odd-sudoku:\n{};", lines.join("\n")));
+    }
+    if !line_rows.is_empty() {
+        sections.push(format!("lines:\n{};", line_rows.join("\n")));
+    }
+    if !little_killers.is_empty() {
+        sections.push(format!("little-killer:\n{};", little_killers.join("\n")));
+    }
+    if sandwich_rows.iter().chain(&sandwich_columns).any(|clue| clue != "-") {
+        sections.push(format!(
+            "sandwich:\nrows: {}\ncolumns: {};",
+            sandwich_rows.join(" "),
+            sandwich_columns.join(" ")
+        ));
+    }
+    if !white.is_empty() || !black.is_empty() {
+        sections.push(format!("kropki:\nwhite: {}\nblack: {};", white.join(" "), black.join(" ")));
+    }
+    if !x.is_empty() || !v.is_empty() {
+        sections.push(format!("xv:\nx: {}\nv: {};", x.join(" "), v.join(" ")));
+    }
+    if !extra_regions.is_empty() {
+        sections.push(format!("extra-regions:\n{};", extra_regions.join("\n")));
+    }
+
+    Ok(sections.join("\n\n"))
+}
+
+/// Returns the JSON of the puzzle out of a link or the compressed puzzle
+fn decode(input: &str) -> Result<String, ParseError> {
+    let input = input.trim();
+    if input.starts_with('{') {
+        return Ok(input.to_string());
+    }
+
+    let data = if let Some(start) = input.find("fpuzzles") {
+        &input[start + "fpuzzles".len()..]
+    } else if let Some(start) = input.find("load=") {
+        &input[start + "load=".len()..]
+    } else if input.contains("://") {
+        // The path of a link like https://sudokupad.app/scl...
+        let path = input.splitn(4, '/').nth(3).unwrap_or("");
+        path.strip_prefix("?puzzleid=").unwrap_or(path)
+    } else {
+        input
+    };
+    let data = data.split('&').next().unwrap_or("");
+
+    if data.starts_with("scl") || data.starts_with("ctc") {
+        return Err(unsupported(
+            "SudokuPad's own format, share the puzzle as f-puzzles link",
+        ));
+    }
+
+    decompress_from_base64(&percent_decode(data))
+        .ok_or_else(|| invalid("The puzzle couldn't be decompressed".to_string()))
+}
+
+/// Decodes the characters of the base64 alphabet, that are escaped in links
+fn percent_decode(data: &str) -> String {
+    data.replace("%2B", "+")
+        .replace("%2F", "/")
+        .replace("%3D", "=")
+        .replace(' ', "+")
+}
+
+/// Returns the `field` section with the given numbers of the grid
+fn field(puzzle: &Map<String, Value>, geometry: Geometry) -> Result<String, ParseError> {
+    let size = geometry.size();
+    let Some(grid) = puzzle.get("grid") else {
+        return Ok(format!("field: {};", "0 ".repeat(size * size).trim_end()));
+    };
+
+    let mut lines = vec![];
+    let rows = grid
+        .as_array()
+        .filter(|rows| rows.len() == size)
+        .ok_or_else(|| invalid(format!("The grid needs {} rows", size)))?;
+    for (collum, row) in rows.iter().enumerate() {
+        let cells = row
+            .as_array()
+            .filter(|cells| cells.len() == size)
+            .ok_or_else(|| invalid(format!("A row of the grid needs {} cells", size)))?;
+
+        let mut numbers = vec![];
+        for (row, cell) in cells.iter().enumerate() {
+            if let Some(region) = cell.get("region").and_then(Value::as_u64) {
+                if region as usize != geometry.box_index(row, collum) {
+                    return Err(unsupported("irregular regions"));
+                }
+            }
+
+            let given = cell.get("given").and_then(Value::as_bool).unwrap_or(false);
+            let number = match cell.get("value").and_then(Value::as_u64) {
+                Some(number) if given && (1..=size as u64).contains(&number) => number as i32,
+                Some(number) if given => return Err(invalid(format!("Invalid number {}", number))),
+                _ => 0,
+            };
+            numbers.push(Geometry::number_to_char(number).to_string());
+        }
+        lines.push(numbers.join(" "));
+    }
+
+    Ok(format!("field:\n{};", lines.join("\n")))
+}
+
+/// Adds the `x-sudoku` marker if both diagonals are set.
+/// Returns a single diagonal as extra region, that has to contain every number once.
+fn diagonals(puzzle: &Map<String, Value>, size: usize, sections: &mut Vec<String>) -> Vec<String> {
+    let is_set = |key| puzzle.get(key).and_then(Value::as_bool).unwrap_or(false);
+
+    match (is_set("diagonal-"), is_set("diagonal+")) {
+        (true, true) => {
+            sections.push("x-sudoku;".to_string());
+            vec![]
+        }
+        (true, false) => vec![(1..=size)
+            .map(|i| format!("r{}c{}", i, i))
+            .collect::<Vec<_>>()
+            .join(" ")],
+        (false, true) => vec![(1..=size)
+            .map(|i| format!("r{}c{}", size + 1 - i, i))
+            .collect::<Vec<_>>()
+            .join(" ")],
+        (false, false) => vec![],
+    }
+}
+
+/// Returns the objects of a constraint like all killer cages
+fn constraints<'a>(value: &'a Value, key: &str) -> Result<Vec<&'a Map<String, Value>>, ParseError> {
+    value
+        .as_array()
+        .and_then(|constraints| constraints.iter().map(Value::as_object).collect())
+        .ok_or_else(|| invalid(format!("{} is not a list of objects", key)))
+}
+
+/// Returns the lines of a constraint like all lines of a thermometer
+fn lines(value: Option<&Value>, size: usize) -> Result<Vec<Vec<String>>, ParseError> {
+    value
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("Missing lines".to_string()))?
+        .iter()
+        .map(|line| cells(Some(line), size))
+        .collect()
+}
+
+/// Returns the cells of a list like `["R1C1", "R1C2"]` in the format of the sections
+fn cells(value: Option<&Value>, size: usize) -> Result<Vec<String>, ParseError> {
+    value
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("Missing cells".to_string()))?
+        .iter()
+        .map(|cell| position(Some(cell), size).map(|(row, collum)| format!("r{}c{}", row, collum)))
+        .collect()
+}
+
+/// Returns the edge between two cells like `["R1C1", "R1C2"]` in the format of the sections
+fn edge(value: Option<&Value>, size: usize) -> Result<String, ParseError> {
+    match cells(value, size)?.as_slice() {
+        [first, second] => Ok(format!("{}-{}", first, second)),
+        _ => Err(invalid("A mark between cells needs two cells".to_string())),
+    }
+}
+
+/// Returns the 1 based row and column of a cell like `R1C2`
+fn position(value: Option<&Value>, size: usize) -> Result<(usize, usize), ParseError> {
+    raw_position(value)
+        .filter(|&(row, collum)| (1..=size).contains(&row) && (1..=size).contains(&collum))
+        .ok_or_else(|| invalid(format!("Invalid cell {}", text(value))))
+}
+
+/// Returns the row and column of a clue outside of the grid like `R0C2`
+fn clue_position(value: Option<&Value>, size: usize) -> Result<(usize, usize), ParseError> {
+    raw_position(value)
+        .filter(|&(row, collum)| row <= size + 1 && collum <= size + 1)
+        .filter(|&(row, collum)| row == 0 || collum == 0 || row == size + 1 || collum == size + 1)
+        .ok_or_else(|| invalid(format!("Invalid clue cell {}", text(value))))
+}
+
+/// Returns the row and column of a cell like `R1C2` without checking if it is in the grid
+fn raw_position(value: Option<&Value>) -> Option<(usize, usize)> {
+    let cell = text(value).to_uppercase();
+    let (row, collum) = cell.strip_prefix('R')?.split_once('C')?;
+    Some((row.parse().ok()?, collum.parse().ok()?))
+}
+
+/// Returns the text of a cell like `R1C2` or nothing if it isn't a text
+fn text(value: Option<&Value>) -> &str {
+    value.and_then(Value::as_str).unwrap_or("")
+}
+
+/// Returns the number of a clue like `"15"` or `15`. A clue without a number is `None`.
+fn clue_value(value: Option<&Value>) -> Result<Option<u32>, ParseError> {
+    let number = match value {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(number)) if number.trim().is_empty() => return Ok(None),
+        Some(Value::String(number)) => number.trim().parse().ok(),
+        Some(Value::Number(number)) => number.as_u64().and_then(|number| number.try_into().ok()),
+        Some(_) => None,
+    };
+
+    let value = value.unwrap_or(&Value::Null);
+    number.map(Some).ok_or_else(|| invalid(format!("The clue {} isn't a number", value)))
+}
+
+/// Returns the sum of a killer cage like it is written in the `killer` section.
+/// The sum is a number like `"15"` or `15`, a bound like `">16"` or unknown (`"?"`).
+fn cage_sum(value: Option<&Value>) -> Result<String, ParseError> {
+    let value = match value {
+        None | Some(Value::Null) => return Ok("?".to_string()),
+        Some(Value::String(sum)) if sum.trim().is_empty() || sum.trim() == "?" => {
+            return Ok("?".to_string())
+        }
+        Some(value) => value,
+    };
+    let sum = match value {
+        Value::String(sum) => {
+            let sum = sum.trim();
+            let number = sum.strip_prefix(['<', '>']).unwrap_or(sum);
+            number.parse::<u32>().ok().map(|_| sum.to_string())
+        }
+        Value::Number(sum) => sum.as_u64().map(|sum| sum.to_string()),
+        _ => None,
+    };
+
+    sum.ok_or_else(|| invalid(format!("The sum {} of a cage isn't a number", value)))
+}
+
+/// Returns true if a constraint isn't used, like `"antiknight": false` or `"arrow": []`
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+fn invalid(message: String) -> ParseError {
+    ParseError::InvalidFPuzzles(message)
+}
+
+fn unsupported(constraint: &str) -> ParseError {
+    ParseError::Unsupported(constraint.to_string())
+}
+
+#[cfg(test)]
+mod f_puzzles_test {
+    use super::{decode, to_sections};
+    use crate::sudokus::{ParseError, RuleRegistry, Sudoku};
+
+    #[test]
+    fn decompress() {
+        // Compressed with LZString.compressToBase64
+        assert_eq!(
+            decode("N4IgzglgXgpiBcBOANCA5gJwgEwQbT2AF9ljSig=").unwrap(),
+            r#"{"size":9,"grid":[[{},{},{}"#
+        );
+
+        let link = "https://sudokupad.app/fpuzzlesN4IgzglgXgpiBcBOANCA5gJwgEwQbT2AF9ljSig%3D";
+        assert_eq!(decode(link).unwrap(), r#"{"size":9,"grid":[[{},{},{}"#);
+
+        assert!(matches!(
+            decode("https://sudokupad.app/sclN4Ig"),
+            Err(ParseError::Unsupported(_))
+        ));
+        assert!(decode("N4Ig!").is_err());
+    }
+
+    #[test]
+    fn sections() {
+        let puzzle = r#"{"size":4,"grid":[[{"value":1,"given":true},{"value":2},{},{}],[{},{},{},{}],[{},{},{},{}],[{},{},{},{}]],
+            "diagonal+":true,"antiknight":false,"arrow":[],
+            "killercage":[{"cells":["R1C1","R2C1"],"value":"5"},{"cells":["R4C4"]}],
+            "thermometer":[{"lines":[["R3C1","R3C2","R3C3"],["R3C1","R4C2"]]}],
+            "odd":[{"cell":"R1C2"}]}"#;
+
+        let sections = to_sections(puzzle).unwrap();
+        assert!(sections.contains("size: 4;\n\nfield:\n1 0 0 0\n0 0 0 0"));
+        assert!(sections.contains("killer:\n5 r1c1 r2c1\n? r4c4;"));
+        assert!(sections.contains("thermo:\nr3c1 r3c2 r3c3\nr3c1 r4c2;"));
+        assert!(sections.contains("even-odd-sudoku:\n. 1 . .\n"));
+        assert!(sections.contains("extra-regions:\nr4c1 r3c2 r2c3 r1c4;"));
+        assert!(!sections.contains("anti-knight"));
+    }
+
+    #[test]
+    fn clue_sections() {
+        let puzzle = r#"{"size":9,
+            "palindrome":[{"lines":[["R1C1","R2C2","R3C3"]]}],
+            "littlekillersum":[{"cell":"R0C2","cells":["R1C3"],"direction":"DR","value":"15"},{"cell":"R10C5","direction":"UR","value":""}],
+            "sandwichsum":[{"cell":"R0C3","value":"5"},{"cell":"R2C0","value":0}],
+            "difference":[{"cells":["R1C1","R1C2"]}],
+            "ratio":[{"cells":["R2C1","R2C2"],"value":"2"}],
+            "xv":[{"cells":["R3C1","R4C1"],"value":"X"},{"cells":["R5C5","R5C6"],"value":"V"}]}"#;
+
+        let sections = to_sections(puzzle).unwrap();
+        assert!(sections.contains("lines:\npalindrome: r1c1 r2c2 r3c3;"));
+        assert!(sections.contains("little-killer:\nr0c2 dr 15;"));
+        assert!(sections.contains("sandwich:\nrows: - 0 - - - - - - -\ncolumns: - - 5 - - - - - -;"));
+        assert!(sections.contains("kropki:\nwhite: r1c1-r1c2\nblack: r2c1-r2c2;"));
+        assert!(sections.contains("xv:\nx: r3c1-r4c1\nv: r5c5-r5c6;"));
+        assert!(Sudoku::parse_with_registry(&sections, &RuleRegistry::default()).is_ok());
+    }
+
+    #[test]
+    fn unsupported() {
+        let puzzle =
+            r#"{"size":9,"quadruple":[{"cells":["R1C1","R1C2","R2C1","R2C2"],"values":[1]}]}"#;
+        assert_eq!(
+            to_sections(puzzle),
+            Err(ParseError::Unsupported("quadruple".to_string()))
+        );
+
+        let puzzle = r#"{"size":9,"arrow":[{"cells":["R1C1","R1C2"],"lines":[["R1C1","R2C3"]]}]}"#;
+        assert!(matches!(
+            to_sections(puzzle),
+            Err(ParseError::Unsupported(_))
+        ));
+
+        // Drawings can have a meaning that is only written in the rules
+        for key in ["line", "circle", "rectangle", "cage"] {
+            let puzzle = format!(r#"{{"size":9,"{}":[{{"cells":["R1C1"]}}]}}"#, key);
+            assert_eq!(to_sections(&puzzle), Err(ParseError::Unsupported(key.to_string())));
+        }
+
+        let puzzle = r#"{"size":9,"ratio":[{"cells":["R1C1","R1C2"],"value":"3"}]}"#;
+        assert!(matches!(
+            to_sections(puzzle),
+            Err(ParseError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_cage() {
+        for sum in [r#""abc""#, r#""-5""#, r#""<""#, "-5", "2.5"] {
+            let puzzle = format!(
+                r#"{{"size":9,"killercage":[{{"cells":["R1C1","R9C9"],"value":{}}}]}}"#,
+                sum
+            );
+            assert!(matches!(
+                to_sections(&puzzle),
+                Err(ParseError::InvalidFPuzzles(_))
+            ));
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use std::fmt;
 use std::fs;
 
+use super::geometry::Geometry;
+use super::line_format;
+use super::parse_error::ParseError;
+
 
 /// FastSudoku is an early copy of sudoku.
 /// Before implementing ohter sudoku variants the only goal was speed.
 /// Due to the code of sudoku getting more complicated I just copied the
 /// code to still have the original and fast sudoku.
+/// It only supports the classic 9x9 Sudoku.
 #[derive(Debug, Clone)]
 pub struct FastSudoku {
     pub field: [[i32; 9]; 9],
@@ -94,6 +99,46 @@ impl FastSudoku {
         return field;
     }    
 
+    /// Returns an Sudoku from a single line with a character for every cell.
+    /// Unset cells are `0` or `.`.
+    /// ```
+    /// use sudoku::sudokus::FastSudoku;
+    ///
+    /// let line = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+    /// let sudoku = FastSudoku::from_line(line).unwrap();
+    /// assert_eq!(sudoku.get_number(0, 0), 4);
+    /// assert_eq!(sudoku.to_line(), line);
+    ///
+    /// // Only the classic 9x9 Sudoku is supported
+    /// assert!(FastSudoku::from_line("1.3.0...........").is_err());
+    /// ```
+    pub fn from_line(line: &str) -> Result<Self, ParseError> {
+        let (geometry, numbers) = line_format::parse_line(line)?;
+        if geometry != Geometry::CLASSIC {
+            return Err(ParseError::InvalidLine(format!(
+                "FastSudoku only supports 81 cells, not {}",
+                numbers.len()
+            )));
+        }
+
+        let mut sudoku = FastSudoku::empty();
+        for (count, number) in numbers.into_iter().enumerate() {
+            sudoku.add_number(count % 9, count / 9, number);
+        }
+        Ok(sudoku)
+    }
+
+    /// Returns all Sudokus of a file with a Sudoku in every line.
+    /// Empty lines and comments starting with `#` are skipped.
+    pub fn lines_from_file(file_path: &str) -> Result<impl Iterator<Item = Result<Self, ParseError>>, ParseError> {
+        Ok(line_format::read_lines(file_path)?.map(|line| FastSudoku::from_line(&line?)))
+    }
+
+    /// Returns the numbers of the Sudoku in a single line. Unset cells are written as `.`
+    pub fn to_line(&self) -> String {
+        line_format::write_line((0..81).map(|count| self.get_number(count % 9, count / 9)))
+    }
+
     /// Returns true, if the field is set. A single Number is writen.
     #[inline]
     pub fn is_set(&self, row: usize, collum: usize) -> bool {
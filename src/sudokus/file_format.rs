@@ -0,0 +1,297 @@
+use std::fmt;
+use std::path::Path;
+
+use super::geometry::Geometry;
+use super::line_format::{parse_line, write_line};
+use super::parse_error::ParseError;
+
+/// Characters of the borders between boxes in the `.ss` format
+const BORDERS: [char; 4] = ['|', '-', '+', '*'];
+
+/// The given numbers and the numbers of the state in the order of the `field` section
+type Numbers = (Geometry, Vec<i32>, Option<Vec<i32>>);
+
+/// Files of other Sudoku programs like HoDoKu or Simple Sudoku.
+/// All of them only have numbers and no rules.
+///
+/// None of them has candidates: every cell is a single character, so there is no room for them.
+/// The grids of candidates that HoDoKu and Simple Sudoku export are read by
+/// `Sudoku::parse_pencil_marks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// A line for every row with a character for every cell. Lines starting with `#` are comments.
+    /// A `.` is an empty cell, so candidates can't be written:
+    /// ```text
+    /// 4.....8.5
+    /// .3.......
+    /// ...
+    /// ```
+    Sdk,
+
+    /// A Sudoku in every line, see `Sudoku::lines_from_file`.
+    /// Like `Sdk` it only has a character for every cell and no candidates.
+    Sdm,
+
+    /// Like `Sdk` with borders between the boxes. Simple Sudoku writes its candidates
+    /// in another grid, not in these files:
+    /// ```text
+    /// 4..|...|8.5
+    /// .3.|...|...
+    /// ...|7..|...
+    /// -----------
+    /// ...
+    /// ```
+    Ss,
+
+    /// The format of SadMan Software Sudoku. The `[Puzzle]` section has the given numbers
+    /// in the `Sdk` format and the optional `[State]` section the numbers found so far.
+    /// The `[State]` has only numbers and no candidates.
+    /// Other sections like `[Properties]` are ignored:
+    /// ```text
+    /// [Puzzle]
+    /// 4.....8.5
+    /// ...
+    /// ```
+    SadMan,
+}
+
+impl fmt::Display for FileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileFormat::Sdk => write!(f, ".sdk"),
+            FileFormat::Sdm => write!(f, ".sdm"),
+            FileFormat::Ss => write!(f, ".ss"),
+            FileFormat::SadMan => write!(f, "SadMan"),
+        }
+    }
+}
+
+impl FileFormat {
+    /// Returns the format of a file by its extension. SadMan files also use `.sdk`,
+    /// they are recognized by their `[Puzzle]` section while parsing.
+    /// ```
+    /// use sudoku::sudokus::FileFormat;
+    ///
+    /// assert_eq!(FileFormat::from_path("puzzles/hard.sdm"), Some(FileFormat::Sdm));
+    /// assert_eq!(FileFormat::from_path("test-sudokus/text/welt-23-11-11-hard-unsolved"), None);
+    /// ```
+    pub fn from_path(path: &str) -> Option<Self> {
+        match Path::new(path)
+            .extension()?
+            .to_str()?
+            .to_lowercase()
+            .as_str()
+        {
+            "sdk" => Some(FileFormat::Sdk),
+            "sdm" => Some(FileFormat::Sdm),
+            "ss" => Some(FileFormat::Ss),
+            _ => None,
+        }
+    }
+
+    /// Returns the format of a file by its extension and its content
+    /// ```
+    /// use sudoku::sudokus::FileFormat;
+    ///
+    /// let path = "test-sudokus/formats/sudoku-com-12-11-12-master-unsolved-sadman.sdk";
+    /// let content = std::fs::read_to_string(path).unwrap();
+    /// assert_eq!(FileFormat::recognize(path, &content), Some(FileFormat::SadMan));
+    /// ```
+    pub fn recognize(path: &str, content: &str) -> Option<Self> {
+        match Self::from_path(path)? {
+            FileFormat::Sdk if Self::is_sad_man(content) => Some(FileFormat::SadMan),
+            format => Some(format),
+        }
+    }
+
+    /// Returns the geometry and the numbers of a Sudoku in this format.
+    /// The numbers of a `[State]` section are returned separately.
+    pub(crate) fn parse(&self, content: &str) -> Result<Numbers, ParseError> {
+        if *self == FileFormat::Sdm {
+            let line = content
+                .lines()
+                .find(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+                .unwrap_or("");
+            let (geometry, numbers) = parse_line(line).map_err(|error| self.error(error))?;
+            return Ok((geometry, numbers, None));
+        }
+
+        if *self == FileFormat::SadMan || Self::is_sad_man(content) {
+            return self.parse_sad_man(content);
+        }
+
+        let (geometry, numbers) = self.parse_grid(content.lines())?;
+        Ok((geometry, numbers, None))
+    }
+
+    /// Writes the numbers of a Sudoku in this format.
+    /// The state is only written in the `SadMan` format.
+    pub(crate) fn write(
+        &self,
+        geometry: Geometry,
+        numbers: &[i32],
+        state: Option<&[i32]>,
+    ) -> String {
+        let size = geometry.size();
+        match self {
+            FileFormat::Sdm => write_line(numbers.iter().copied()) + "\n",
+            FileFormat::Sdk => Self::write_grid(geometry, numbers, false),
+            FileFormat::Ss => Self::write_grid(geometry, numbers, true),
+            FileFormat::SadMan => {
+                let mut output = "[Puzzle]\n".to_string();
+                output.push_str(&Self::write_grid(geometry, numbers, false));
+                if let Some(state) = state.filter(|state| *state != numbers) {
+                    debug_assert!(state.len() == size * size);
+                    output.push_str("[State]\n");
+                    output.push_str(&Self::write_grid(geometry, state, false));
+                }
+                output
+            }
+        }
+    }
+
+    fn is_sad_man(content: &str) -> bool {
+        content.lines().any(|line| line.trim() == "[Puzzle]")
+    }
+
+    /// Parses the sections of a SadMan file
+    fn parse_sad_man(&self, content: &str) -> Result<Numbers, ParseError> {
+        let mut puzzle = None;
+        let mut state = None;
+        let mut section = "";
+        let mut lines = vec![];
+
+        for line in content.lines().chain(std::iter::once("[End]")) {
+            let line = line.trim();
+            if !(line.starts_with('[') && line.ends_with(']')) {
+                lines.push(line);
+                continue;
+            }
+
+            match section {
+                "[Puzzle]" => puzzle = Some(self.parse_grid(lines.drain(..))?),
+                "[State]" => state = Some(self.parse_grid(lines.drain(..))?),
+                _ => lines.clear(),
+            }
+            section = line;
+        }
+
+        let (geometry, numbers) = puzzle.ok_or_else(|| {
+            self.error(ParseError::InvalidLine("No [Puzzle] section".to_string()))
+        })?;
+        match state {
+            Some((state_geometry, _)) if state_geometry != geometry => {
+                Err(self.error(ParseError::InvalidLine(
+                    "The [State] has another size than the [Puzzle]".to_string(),
+                )))
+            }
+            _ => Ok((geometry, numbers, state.map(|(_, state)| state))),
+        }
+    }
+
+    /// Parses a grid with a line for every row. Comments, whitespace and borders are ignored.
+    fn parse_grid<'a>(
+        &self,
+        lines: impl Iterator<Item = &'a str>,
+    ) -> Result<(Geometry, Vec<i32>), ParseError> {
+        let cells: String = lines
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.chars())
+            .filter(|c| !c.is_whitespace() && !BORDERS.contains(c))
+            .collect();
+
+        parse_line(&cells).map_err(|error| self.error(error))
+    }
+
+    /// Writes a line for every row, with borders between the boxes if needed
+    fn write_grid(geometry: Geometry, numbers: &[i32], borders: bool) -> String {
+        let size = geometry.size();
+        let mut output = String::new();
+
+        for (collum, row_numbers) in numbers.chunks(size).enumerate() {
+            if borders && collum != 0 && collum % geometry.box_height() == 0 {
+                let width = size + size / geometry.box_width() - 1;
+                output.push_str(&"-".repeat(width));
+                output.push('\n');
+            }
+
+            for (row, cells) in row_numbers.chunks(geometry.box_width()).enumerate() {
+                if borders && row != 0 {
+                    output.push('|');
+                }
+                output.push_str(&write_line(cells.iter().copied()));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    fn error(&self, error: ParseError) -> ParseError {
+        match error {
+            ParseError::InvalidLine(message) => ParseError::InvalidFormat {
+                format: *self,
+                message,
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod file_format_test {
+    use super::FileFormat;
+    use crate::sudokus::{Geometry, ParseError};
+
+    fn numbers() -> Vec<i32> {
+        (0..81).map(|count| count % 10).collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        for format in [
+            FileFormat::Sdk,
+            FileFormat::Sdm,
+            FileFormat::Ss,
+            FileFormat::SadMan,
+        ] {
+            let output = format.write(Geometry::CLASSIC, &numbers(), None);
+            let (geometry, parsed, state) = format.parse(&output).unwrap();
+            assert_eq!(geometry, Geometry::CLASSIC);
+            assert_eq!(parsed, numbers());
+            assert_eq!(state, None);
+        }
+    }
+
+    #[test]
+    fn ss() {
+        let geometry = Geometry::new(6, 3, 2).unwrap();
+        let numbers: Vec<i32> = (0..36).map(|count| count % 7).collect();
+
+        let output = FileFormat::Ss.write(geometry, &numbers, None);
+        assert_eq!(output.lines().next(), Some(".12|345"));
+        assert_eq!(output.lines().nth(2), Some("-------"));
+    }
+
+    #[test]
+    fn sad_man() {
+        let mut state = numbers();
+        state[0] = 5;
+
+        let output = FileFormat::SadMan.write(Geometry::CLASSIC, &numbers(), Some(&state));
+        let content = format!("[Properties]\nName=Test\n{}", output);
+
+        // SadMan files are also recognized with the .sdk extension
+        let (_, parsed, parsed_state) = FileFormat::Sdk.parse(&content).unwrap();
+        assert_eq!(parsed, numbers());
+        assert_eq!(parsed_state, Some(state));
+
+        assert!(matches!(
+            FileFormat::SadMan.parse("[State]\n..."),
+            Err(ParseError::InvalidFormat {
+                format: FileFormat::SadMan,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,205 @@
+/// Identifier of the section with the size of a Sudoku
+pub(crate) const SIZE_IDENTIFIER: &str = "size";
+
+/// Size of a Sudoku and the shape of its boxes.
+///
+/// A Sudoku of size `n` has `n` rows, `n` columns and `n` boxes, that are filled
+/// with the numbers 1 to `n`. Numbers above 9 are written as letters (`A` = 10, `B` = 11, ...).
+///
+/// It is set in the `size` section of a file. Without a `box` line the boxes are as
+/// square as possible and wider than high. Without the section a classic 9x9 Sudoku is used:
+/// ```text
+/// size: 6
+/// box: 3x2;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Geometry {
+    size: usize,
+    box_width: usize,
+    box_height: usize,
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+impl Geometry {
+    /// The classic 9x9 Sudoku with 3x3 boxes
+    pub const CLASSIC: Geometry = Geometry {
+        size: 9,
+        box_width: 3,
+        box_height: 3,
+    };
+
+    /// The biggest supported size. All numbers of a cell have to fit in an `u32`.
+    pub const MAX_SIZE: usize = 25;
+
+    /// Returns a geometry with boxes of `box_width` columns and `box_height` rows.
+    /// The boxes have to fill the field exactly.
+    /// ```
+    /// use sudoku::sudokus::Geometry;
+    ///
+    /// assert!(Geometry::new(6, 3, 2).is_some());
+    /// assert!(Geometry::new(6, 3, 3).is_none());
+    /// ```
+    pub fn new(size: usize, box_width: usize, box_height: usize) -> Option<Self> {
+        if size == 0 || size > Self::MAX_SIZE || box_width * box_height != size {
+            return None;
+        }
+
+        Some(Self {
+            size,
+            box_width,
+            box_height,
+        })
+    }
+
+    /// Returns a geometry with boxes that are as square as possible
+    /// ```
+    /// use sudoku::sudokus::Geometry;
+    ///
+    /// let geometry = Geometry::with_size(12).unwrap();
+    /// assert_eq!((geometry.box_width(), geometry.box_height()), (4, 3));
+    /// ```
+    pub fn with_size(size: usize) -> Option<Self> {
+        let box_height = (1..=size)
+            .filter(|height| height * height <= size && size.is_multiple_of(*height))
+            .max()?;
+
+        Self::new(size, size / box_height, box_height)
+    }
+
+    /// Parses the `size` section like `size: 6\nbox: 3x2`
+    pub(crate) fn from_str(input: &str) -> Option<Self> {
+        let after_identifier = &input[input.find(SIZE_IDENTIFIER)? + SIZE_IDENTIFIER.len()..];
+        let mut lines = after_identifier.trim_start().strip_prefix(':')?.lines();
+
+        let size = lines.next()?.trim().parse().ok()?;
+
+        for line in lines {
+            let Some((name, shape)) = line.split_once(':') else {
+                continue;
+            };
+            if name.trim() != "box" {
+                return None;
+            }
+
+            let (width, height) = shape.trim().split_once('x')?;
+            return Self::new(size, width.parse().ok()?, height.parse().ok()?);
+        }
+
+        Self::with_size(size)
+    }
+
+    /// Number of rows, columns and boxes
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of columns of a box
+    #[inline]
+    pub fn box_width(&self) -> usize {
+        self.box_width
+    }
+
+    /// Number of rows of a box
+    #[inline]
+    pub fn box_height(&self) -> usize {
+        self.box_height
+    }
+
+    /// Returns all numbers in the raw format
+    /// ```
+    /// use sudoku::sudokus::Geometry;
+    ///
+    /// assert_eq!(Geometry::CLASSIC.all(), 0b111_111_111);
+    /// ```
+    #[inline]
+    pub fn all(&self) -> u32 {
+        (0b1 << self.size) - 1
+    }
+
+    /// Returns the index of the box of a cell. Boxes are counted like they are written in the file.
+    #[inline]
+    pub fn box_index(&self, row: usize, collum: usize) -> usize {
+        debug_assert!(row < self.size && collum < self.size);
+        collum / self.box_height * (self.size / self.box_width) + row / self.box_width
+    }
+
+    /// Returns all cells of a box
+    pub fn box_cells(&self, index: usize) -> impl Iterator<Item = (usize, usize)> {
+        debug_assert!(index < self.size);
+        let first_row = index % (self.size / self.box_width) * self.box_width;
+        let first_collum = index / (self.size / self.box_width) * self.box_height;
+        let box_width = self.box_width;
+
+        (0..self.size).map(move |i| (first_row + i % box_width, first_collum + i / box_width))
+    }
+
+    /// Returns the character of a number. Numbers above 9 are letters.
+    /// ```
+    /// use sudoku::sudokus::Geometry;
+    ///
+    /// assert_eq!(Geometry::number_to_char(7), '7');
+    /// assert_eq!(Geometry::number_to_char(16), 'G');
+    /// ```
+    pub fn number_to_char(number: i32) -> char {
+        debug_assert!((0..=Self::MAX_SIZE as i32).contains(&number));
+        match number {
+            0..=9 => char::from(b'0' + number as u8),
+            _ => char::from(b'A' + (number - 10) as u8),
+        }
+    }
+
+    /// Returns the number of a character. An empty cell is written as `0` or `.`.
+    /// ```
+    /// use sudoku::sudokus::Geometry;
+    ///
+    /// assert_eq!(Geometry::char_to_number('.'), Some(0));
+    /// assert_eq!(Geometry::char_to_number('A'), Some(10));
+    /// assert_eq!(Geometry::char_to_number('-'), None);
+    /// ```
+    pub fn char_to_number(c: char) -> Option<i32> {
+        match c {
+            '.' => Some(0),
+            '0'..='9' => Some(c as i32 - '0' as i32),
+            'A'..='P' => Some(c as i32 - 'A' as i32 + 10),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod geometry_test {
+    use super::Geometry;
+
+    #[test]
+    fn parse() {
+        assert_eq!(Geometry::from_str("size: 9"), Some(Geometry::CLASSIC));
+        assert_eq!(
+            Geometry::from_str("size: 6\nbox: 2x3"),
+            Geometry::new(6, 2, 3)
+        );
+        assert_eq!(Geometry::from_str("size: 16"), Geometry::new(16, 4, 4));
+        assert_eq!(Geometry::from_str("size: 6\nbox: 4x2"), None);
+        assert_eq!(Geometry::from_str("size: 26"), None);
+    }
+
+    #[test]
+    fn boxes() {
+        let geometry = Geometry::new(6, 3, 2).unwrap();
+
+        // The second box is right of the first one
+        assert_eq!(geometry.box_index(3, 1), 1);
+        assert_eq!(geometry.box_index(2, 2), 2);
+
+        let cells: Vec<_> = geometry.box_cells(3).collect();
+        assert_eq!(cells, vec![(3, 2), (4, 2), (5, 2), (3, 3), (4, 3), (5, 3)]);
+        assert!(cells
+            .iter()
+            .all(|&(row, collum)| geometry.box_index(row, collum) == 3));
+    }
+}
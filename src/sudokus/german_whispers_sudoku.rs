@@ -0,0 +1,127 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::lines::{parse_lines, LINES_IDENTIFIER};
+use super::parse_error::ParseError;
+use super::sudoku_rule::{all_numbers, candidates, related_numbers, SudokuRule};
+
+/// German Whispers Sudoku
+///
+/// Neighbours on a line differ by at least 5. In Sudokus of other sizes they differ by at
+/// least half of the size rounded up. For the data structure see `parse_lines`:
+/// ```text
+/// lines:
+/// whisper: r1c1 r1c2 r2c3;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GermanWhispersSudoku {
+    lines: Vec<Vec<(usize, usize)>>,
+    difference: u32,
+}
+
+impl SudokuRule for GermanWhispersSudoku {
+    fn str_identifier() -> &'static str {
+        "whisper"
+    }
+
+    fn section_identifier() -> &'static str {
+        LINES_IDENTIFIER
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        Ok(Box::new(Self {
+            lines: parse_lines(input, Self::str_identifier(), geometry.size())?,
+            difference: (geometry.size() as u32).div_ceil(2),
+        }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for line in &self.lines {
+            for pair in line.windows(2) {
+                let first = field[pair[0].0][pair[0].1];
+                let second = field[pair[1].0][pair[1].1];
+                if first.count_ones() != 1 || second.count_ones() != 1 {
+                    continue;
+                }
+
+                if first.trailing_zeros().abs_diff(second.trailing_zeros()) < self.difference {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let mut possible = all_numbers(field);
+
+        for line in &self.lines {
+            for (i, &cell) in line.iter().enumerate() {
+                if cell != (row, collum) {
+                    continue;
+                }
+
+                let neighbours = line[i.saturating_sub(1)..(i + 2).min(line.len())].iter();
+                for &(other_row, other_collum) in neighbours.filter(|&&other| other != cell) {
+                    let other = candidates(field, other_row, other_collum);
+                    possible &= related_numbers(other, field.len(), |a, b| {
+                        a.abs_diff(b) >= self.difference
+                    });
+                }
+            }
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        for line in &self.lines {
+            drawing.cell_line(line, "#5cb85c", 0.15);
+        }
+    }
+}
+
+#[cfg(test)]
+mod german_whispers_sudoku_test {
+    use super::GermanWhispersSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b1;
+        field[1][1] = 0b1_000_000;
+        field[2][1] = 0b10;
+
+        let whisper = GermanWhispersSudoku::from_str("lines:\nwhisper: r1c1 r2c2 r2c3", Geometry::CLASSIC);
+        assert!(whisper.complys(&field));
+
+        field[0][0] = 0b100;
+        assert!(!whisper.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let mut field = vec![vec![0; 9]; 9];
+        let whisper = GermanWhispersSudoku::from_str("lines:\nwhisper: r1c1 r2c2 r2c3", Geometry::CLASSIC);
+
+        // A 5 can never be on a whisper
+        assert_eq!(whisper.get_not_possible_numbers_raw(&field, 0, 0), 0b10_000);
+
+        // Between a 1 and a 2 there can only be a 7, 8 or 9
+        field[0][0] = 0b1;
+        field[2][1] = 0b10;
+        assert_eq!(whisper.get_not_possible_numbers_raw(&field, 1, 1), 0b000_111_111);
+
+        // Nothing fits between a 3 and a 8
+        field[0][0] = 0b100;
+        field[2][1] = 0b10_000_000;
+        assert_eq!(whisper.get_not_possible_numbers_raw(&field, 1, 1), 0b111_111_111);
+    }
+}
@@ -0,0 +1,309 @@
+use std::cell::RefCell;
+
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{
+    all_numbers, candidates, invalid_section, parse_cells, reachable_distinct_sums,
+    section_content, SudokuRule,
+};
+
+/// The candidates of the cells of a cage with their possible numbers
+type Cached = Option<(Vec<u32>, Vec<u32>)>;
+
+/// A group of cells with different numbers. Their sum has to be between `min` and `max`.
+#[derive(Debug, Clone)]
+struct Cage {
+    cells: Vec<(usize, usize)>,
+    min: i32,
+    max: i32,
+}
+
+/// Killer Sudoku
+///
+/// Every cage has its own line with its sum and its cells. Numbers can't repeat in a cage.
+/// The sum can be exact (`15`), unknown (`?`) or a bound (`<10`, `>20`).
+/// The `field` section can be empty, if the cages are enough to solve the Sudoku:
+/// ```text
+/// killer:
+/// 15 r1c1 r1c2
+/// ? r2c1 r2c2 r2c3
+/// <10 r3c3 r4c3
+/// >20 r5c5 r5c6 r5c7;
+/// ```
+#[derive(Debug, Clone)]
+pub struct KillerSudoku {
+    cages: Vec<Cage>,
+
+    /// The possible numbers of a cage only depend on the candidates of its cells.
+    /// They are needed for every cell of the cage, so the last ones of every cage are cached.
+    cache: RefCell<Vec<Cached>>,
+}
+
+impl SudokuRule for KillerSudoku {
+    fn str_identifier() -> &'static str {
+        "killer"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let mut cages = vec![];
+        for line in section_content(input, Self::str_identifier()).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            cages.push(Self::parse_cage(line, geometry.size()).ok_or_else(|| {
+                invalid_section(Self::str_identifier(), format!("Invalid cage {}", line))
+            })?);
+        }
+
+        Ok(Box::new(Self {
+            cache: RefCell::new(vec![None; cages.len()]),
+            cages,
+        }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for cage in &self.cages {
+            let mut sum = 0;
+            let mut unset = 0;
+            let mut set_numbers = 0;
+            for &(row, collum) in &cage.cells {
+                if field[row][collum].count_ones() != 1 {
+                    unset += 1;
+                    continue;
+                }
+                if set_numbers & field[row][collum] != 0 {
+                    return false;
+                }
+
+                set_numbers |= field[row][collum];
+                sum += field[row][collum].trailing_zeros() as i32 + 1;
+            }
+
+            if sum + unset > cage.max || sum + field.len() as i32 * unset < cage.min {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(&self, field: &[Vec<u32>], row: usize, collum: usize) -> u32 {
+        let mut possible = all_numbers(field);
+
+        for (index, cage) in self.cages.iter().enumerate() {
+            let Some(position) = cage.cells.iter().position(|&cell| cell == (row, collum)) else {
+                continue;
+            };
+
+            let candidates: Vec<_> = cage
+                .cells
+                .iter()
+                .map(|&(r, c)| candidates(field, r, c))
+                .collect();
+
+            let cached = match &self.cache.borrow()[index] {
+                Some((cached_candidates, possible)) if *cached_candidates == candidates => {
+                    Some(possible[position])
+                }
+                _ => None,
+            };
+            let possible_in_cell = match cached {
+                Some(possible_in_cell) => possible_in_cell,
+                None => {
+                    let possible_in_cage = cage.possible_numbers(&candidates, field.len());
+                    let possible_in_cell = possible_in_cage[position];
+                    self.cache.borrow_mut()[index] = Some((candidates, possible_in_cage));
+                    possible_in_cell
+                }
+            };
+
+            possible &= possible_in_cell;
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    fn houses(&self, _size: usize) -> Vec<Vec<(usize, usize)>> {
+        self.cages.iter().map(|cage| cage.cells.clone()).collect()
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        for cage in &self.cages {
+            drawing.cage(&cage.cells, cage.label().as_deref());
+        }
+    }
+}
+
+impl KillerSudoku {
+    /// Parses a cage like `15 r1c1 r1c2`, `? r1c1 r1c2` or `<10 r1c1 r1c2`.
+    /// A sum or bound has to be between 1 and the biggest sum of the cage.
+    fn parse_cage(input: &str, size: usize) -> Option<Cage> {
+        let (sum, cells) = input.trim().split_once(char::is_whitespace)?;
+        let cells = parse_cells(cells, size)?;
+
+        let mut sorted = cells.clone();
+        sorted.sort();
+        sorted.dedup();
+        if cells.is_empty() || sorted.len() != cells.len() || cells.len() > size {
+            return None;
+        }
+
+        // The smallest and the biggest numbers in every cell
+        let smallest_sum = (1..=cells.len()).sum::<usize>() as i32;
+        let biggest_sum = (size - cells.len() + 1..=size).sum::<usize>() as i32;
+        let bound = |sum: &str, sums: std::ops::RangeInclusive<i32>| {
+            sum.parse::<i32>().ok().filter(|sum| sums.contains(sum))
+        };
+        let (min, max) = match sum {
+            "?" => (0, i32::MAX),
+            _ if sum.starts_with('<') => (0, bound(&sum[1..], smallest_sum + 1..=biggest_sum)? - 1),
+            _ if sum.starts_with('>') => (bound(&sum[1..], 1..=biggest_sum - 1)? + 1, i32::MAX),
+            _ => {
+                let sum = bound(sum, smallest_sum..=biggest_sum)?;
+                (sum, sum)
+            }
+        };
+
+        Some(Cage { cells, min, max })
+    }
+}
+
+impl Cage {
+    /// Returns the sum like it is written in the section. An unknown sum has no label.
+    fn label(&self) -> Option<String> {
+        match (self.min, self.max) {
+            (0, i32::MAX) => None,
+            (0, max) => Some(format!("<{}", max + 1)),
+            (min, i32::MAX) => Some(format!(">{}", min - 1)),
+            (min, _) => Some(min.to_string()),
+        }
+    }
+
+    /// Returns the possible numbers of all cells of the cage in the raw format
+    fn possible_numbers(&self, candidates: &[u32], size: usize) -> Vec<u32> {
+        let mut possible = vec![0; candidates.len()];
+
+        for (index, &candidate) in candidates.iter().enumerate() {
+            let mut others = candidates.to_vec();
+            others.remove(index);
+
+            for num in 0..size {
+                if candidate & (0b1 << num) == 0 {
+                    continue;
+                }
+
+                let sums = reachable_distinct_sums(&others, 0b1 << num);
+                let number = num as i32 + 1;
+                let min = self.min.saturating_sub(number).max(0);
+                let max = self.max.saturating_sub(number);
+                let max_sum = (others.len() * size) as i32;
+                if (min..=max.min(max_sum)).any(|sum| sums.contains(sum)) {
+                    possible[index] |= 0b1 << num;
+                }
+            }
+        }
+
+        possible
+    }
+}
+
+#[cfg(test)]
+mod killer_sudoku_test {
+    use super::KillerSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn parse() {
+        let killer = KillerSudoku::from_str(
+            "killer:\n15 r1c1 r1c2\n? r2c1\n<10 r3c3 r4c3\n>20 r5c5 r5c6 r5c7",
+            Geometry::CLASSIC,
+        );
+
+        let sums: Vec<_> = killer
+            .cages
+            .iter()
+            .map(|cage| (cage.min, cage.max))
+            .collect();
+        assert_eq!(sums, vec![(15, 15), (0, i32::MAX), (0, 9), (21, i32::MAX)]);
+        assert_eq!(killer.cages[2].cells, vec![(2, 2), (2, 3)]);
+
+        // The sums are drawn like they are written
+        let labels: Vec<_> = killer.cages.iter().map(|cage| cage.label()).collect();
+        assert_eq!(labels, vec![Some("15".to_string()), None, Some("<10".to_string()), Some(">20".to_string())]);
+
+        assert!(KillerSudoku::parse_cage("15 r1c1 r1c1", 9).is_none());
+        assert!(KillerSudoku::parse_cage("15", 9).is_none());
+
+        // Sums and bounds have to be possible for the cells of the cage
+        assert!(KillerSudoku::parse_cage("17 r1c1 r1c2", 9).is_some());
+        assert!(KillerSudoku::parse_cage(">16 r1c1 r1c2", 9).is_some());
+        assert!(KillerSudoku::parse_cage("<4 r1c1 r1c2", 9).is_some());
+        assert!(KillerSudoku::parse_cage(">17 r1c1 r1c2", 9).is_none());
+        assert!(KillerSudoku::parse_cage("2 r1c1 r1c2", 9).is_none());
+        assert!(KillerSudoku::parse_cage("<3 r1c1 r1c2", 9).is_none());
+        for sum in ["18", "<18", ">18", "0", "<0", ">-5", "-3", ">2147483647", "<-2147483648"] {
+            assert!(KillerSudoku::parse_cage(&format!("{} r1c1 r1c2", sum), 9).is_none());
+        }
+    }
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b1;
+        field[1][0] = 0b10;
+
+        let exact = KillerSudoku::from_str("killer:\n3 r1c1 r1c2", Geometry::CLASSIC);
+        let less = KillerSudoku::from_str("killer:\n<4 r1c1 r1c2", Geometry::CLASSIC);
+        let unknown = KillerSudoku::from_str("killer:\n? r1c1 r1c2", Geometry::CLASSIC);
+        assert!(exact.complys(&field));
+        assert!(less.complys(&field));
+        assert!(unknown.complys(&field));
+
+        field[1][0] = 0b100;
+        assert!(!exact.complys(&field));
+        assert!(!less.complys(&field));
+
+        // Numbers can't repeat in a cage
+        field[1][0] = 0b1;
+        assert!(!unknown.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let field = vec![vec![0; 9]; 9];
+
+        // 3 in two cells is always 1 and 2
+        let killer = KillerSudoku::from_str("killer:\n3 r1c1 r1c2", Geometry::CLASSIC);
+        assert_eq!(
+            killer.get_not_possible_numbers_raw(&field, 0, 0),
+            0b111_111_100
+        );
+        assert_eq!(killer.get_not_possible_numbers_raw(&field, 1, 1), 0);
+
+        // Only the last candidates of every cage are cached
+        let mut other = field.clone();
+        other[1][0] = 0b1;
+        assert_eq!(
+            killer.get_not_possible_numbers_raw(&other, 0, 0),
+            0b111_111_101
+        );
+        assert_eq!(killer.cache.borrow().len(), 1);
+
+        // 9 and 8 are too big to be in a sum of at most 10 with two other cells
+        let killer = KillerSudoku::from_str("killer:\n<11 r1c1 r1c2 r1c3", Geometry::CLASSIC);
+        assert_eq!(
+            killer.get_not_possible_numbers_raw(&field, 2, 0),
+            0b110_000_000
+        );
+
+        // At least 17 with two cells is only possible with 8 and 9
+        let killer = KillerSudoku::from_str("killer:\n>16 r1c1 r1c2", Geometry::CLASSIC);
+        assert_eq!(
+            killer.get_not_possible_numbers_raw(&field, 0, 0),
+            0b001_111_111
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use super::drawing::Drawing;
+use super::edge_marks::{EdgeMark, EdgeMarks};
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{all_numbers, section_content, SudokuRule};
+
+/// Dots between two cells of a Kropki Sudoku
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Dot {
+    /// The numbers are consecutive
+    White,
+    /// One number is the double of the other
+    Black,
+}
+
+impl EdgeMark for Dot {
+    const ALL: &'static [Self] = &[Dot::White, Dot::Black];
+
+    fn name(self) -> &'static str {
+        match self {
+            Dot::White => "white",
+            Dot::Black => "black",
+        }
+    }
+
+    fn related(self, first: i32, second: i32) -> bool {
+        match self {
+            Dot::White => first.abs_diff(second) == 1,
+            Dot::Black => first == 2 * second || second == 2 * first,
+        }
+    }
+
+    fn draw(self, drawing: &mut Drawing, position: (f64, f64)) {
+        let fill = match self {
+            Dot::White => "#ffffff",
+            Dot::Black => "#000000",
+        };
+        drawing.circle(position, 0.12, fill, "#000000");
+    }
+}
+
+/// Kropki Sudoku
+///
+/// For the data structure see `EdgeMarks`:
+/// ```text
+/// kropki:
+/// white: r1c3-r1c4 r4c4-r4c5
+/// black: r2c1-r2c2
+/// negative;
+/// ```
+#[derive(Debug, Clone)]
+pub struct KropkiSudoku {
+    dots: EdgeMarks<Dot>,
+}
+
+impl SudokuRule for KropkiSudoku {
+    fn str_identifier() -> &'static str {
+        "kropki"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        Ok(Box::new(Self {
+            dots: EdgeMarks::from_str(
+                section_content(input, Self::str_identifier()),
+                Self::str_identifier(),
+                geometry.size(),
+            )?,
+        }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        self.dots.complys(field)
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        all_numbers(field) & !self.dots.possible_numbers(field, row, collum)
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        self.dots.draw(drawing);
+    }
+}
+
+#[cfg(test)]
+mod kropki_sudoku_test {
+    use super::KropkiSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        // 3 4 8 in the first line
+        field[0][0] = 0b100;
+        field[1][0] = 0b1000;
+        field[2][0] = 0b10_000_000;
+
+        let good = KropkiSudoku::from_str("kropki:\nwhite: r1c1-r1c2\nblack: r1c2-r1c3\nnegative", Geometry::CLASSIC);
+        let wrong_color = KropkiSudoku::from_str("kropki:\nblack: r1c1-r1c2 r1c2-r1c3", Geometry::CLASSIC);
+        let missing = KropkiSudoku::from_str("kropki:\nwhite: r1c1-r1c2\nnegative", Geometry::CLASSIC);
+
+        assert!(good.complys(&field));
+        assert!(!wrong_color.complys(&field));
+        assert!(!missing.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b1000;
+
+        let kropki = KropkiSudoku::from_str("kropki:\nwhite: r1c1-r1c2\nblack: r1c1-r2c1\nnegative", Geometry::CLASSIC);
+
+        // Next to a 4 with a white dot is a 3 or a 5
+        assert_eq!(kropki.get_not_possible_numbers_raw(&field, 1, 0), 0b111_101_011);
+        // Next to a 4 with a black dot is a 2 or a 8
+        assert_eq!(kropki.get_not_possible_numbers_raw(&field, 0, 1), 0b101_111_101);
+        // Without a dot it can't be 2, 3, 5 or 8
+        field[1][1] = 0b1000;
+        assert_eq!(kropki.get_not_possible_numbers_raw(&field, 2, 1), 0b010_010_110);
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+
+/// Returns the geometry and the numbers of a Sudoku written in a single line.
+///
+/// Every cell is a single character in the same order as in the `field` section.
+/// Unset cells are `0` or `.` and everything after the first whitespace is ignored:
+/// ```text
+/// 4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......
+/// ```
+pub(crate) fn parse_line(line: &str) -> Result<(Geometry, Vec<i32>), ParseError> {
+    let puzzle = line.split_whitespace().next().unwrap_or("");
+    let cells = puzzle.chars().count();
+
+    let geometry = (1..=Geometry::MAX_SIZE)
+        .find(|size| size * size == cells)
+        .and_then(Geometry::with_size)
+        .ok_or_else(|| {
+            ParseError::InvalidLine(format!("{} cells are not a square field", cells))
+        })?;
+
+    let numbers = puzzle
+        .chars()
+        .map(|c| match Geometry::char_to_number(c) {
+            Some(number) if number as usize <= geometry.size() => Ok(number),
+            _ => Err(ParseError::InvalidLine(format!("Invalid cell {}", c))),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((geometry, numbers))
+}
+
+/// Writes the numbers in a single line. Unset cells are written as `.`
+pub(crate) fn write_line(numbers: impl Iterator<Item = i32>) -> String {
+    numbers
+        .map(|number| match number {
+            0 => '.',
+            _ => Geometry::number_to_char(number),
+        })
+        .collect()
+}
+
+/// Returns the lines of a file with a Sudoku in every line.
+/// Empty lines and comments starting with `#` are skipped.
+pub(crate) fn read_lines(
+    file_path: &str,
+) -> Result<impl Iterator<Item = Result<String, ParseError>>, ParseError> {
+    let file = File::open(file_path).map_err(|error| ParseError::Io(error.to_string()))?;
+
+    Ok(BufReader::new(file)
+        .lines()
+        .map(|line| line.map_err(|error| ParseError::Io(error.to_string())))
+        .filter(|line| {
+            line.as_ref().map_or(true, |line| {
+                !line.trim().is_empty() && !line.trim_start().starts_with('#')
+            })
+        }))
+}
+
+#[cfg(test)]
+mod line_format_test {
+    use super::{parse_line, write_line};
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn parse() {
+        let (geometry, numbers) = parse_line("1.3.04..........  # comment").unwrap();
+        assert_eq!(geometry, Geometry::new(4, 2, 2).unwrap());
+        assert_eq!(numbers.len(), 16);
+        assert_eq!(numbers[..4], [1, 0, 3, 0]);
+
+        // G is 16, that is too big for a 4x4 Sudoku
+        assert!(parse_line("1.3.0G..........").is_err());
+        assert!(parse_line("1.3.0.........").is_err());
+        assert!(parse_line("").is_err());
+    }
+
+    #[test]
+    fn write() {
+        assert_eq!(write_line([1, 0, 9, 16].into_iter()), "1.9G");
+    }
+}
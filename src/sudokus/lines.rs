@@ -0,0 +1,106 @@
+use super::parse_error::ParseError;
+use std::collections::HashSet;
+use super::sudoku_rule::{invalid_section, parse_cells, section_content};
+
+/// Identifier of the section with all line based rules
+pub(crate) const LINES_IDENTIFIER: &str = "lines";
+
+/// Kinds of lines the `lines` section can contain
+const LINE_KINDS: [&str; 3] = ["whisper", "renban", "palindrome"];
+
+/// Parses all lines of a kind out of the `lines` section.
+///
+/// Every line has its own row. It starts with the kind of the line and
+/// is followed by its cells. Following cells have to touch, at least diagonally:
+/// ```text
+/// lines:
+/// whisper: r1c1 r1c2 r2c3
+/// renban: r4c4 r4c5 r4c6
+/// palindrome: r7c1 r8c2 r9c3;
+/// ```
+pub(crate) fn parse_lines(
+    input: &str,
+    kind: &str,
+    size: usize,
+) -> Result<Vec<Vec<(usize, usize)>>, ParseError> {
+    let mut lines = vec![];
+    for line in section_content(input, LINES_IDENTIFIER).lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let Some((line_kind, cells)) = line.split_once(':') else {
+            return Err(invalid_section(LINES_IDENTIFIER, format!("Missing kind in {}", line)));
+        };
+        if !LINE_KINDS.contains(&line_kind.trim()) {
+            return Err(invalid_section(
+                LINES_IDENTIFIER,
+                format!("Unknown kind of line {}", line_kind.trim()),
+            ));
+        }
+        if line_kind.trim() != kind {
+            continue;
+        }
+
+        let cells = parse_cells(cells, size)
+            .ok_or_else(|| invalid_section(LINES_IDENTIFIER, format!("Invalid cell in {}", line)))?;
+        for pair in cells.windows(2) {
+            if pair[0] == pair[1]
+                || pair[0].0.abs_diff(pair[1].0) > 1
+                || pair[0].1.abs_diff(pair[1].1) > 1
+            {
+                return Err(invalid_section(
+                    LINES_IDENTIFIER,
+                    "Cells of a line have to touch each other",
+                ));
+            }
+        }
+        let mut seen = HashSet::new();
+        if !cells.iter().all(|cell| seen.insert(cell)) {
+            return Err(invalid_section(LINES_IDENTIFIER, "A line can't contain a cell twice"));
+        }
+
+        lines.push(cells);
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod lines_test {
+    use super::parse_lines;
+    use crate::sudokus::ParseError;
+
+    #[test]
+    fn parse() {
+        let input = "lines:\nwhisper: r1c1 r2c2\nrenban: r5c5 r5c6 r5c7\nwhisper: r9c9 r9c8";
+
+        assert_eq!(
+            parse_lines(input, "whisper", 9),
+            Ok(vec![vec![(0, 0), (1, 1)], vec![(8, 8), (7, 8)]])
+        );
+        assert_eq!(parse_lines(input, "renban", 9), Ok(vec![vec![(4, 4), (5, 4), (6, 4)]]));
+        assert_eq!(parse_lines(input, "palindrome", 9), Ok(vec![]));
+    }
+
+    #[test]
+    fn invalid() {
+        // An unknown cell, a cell outside of the field, cells that don't touch and a repeated cell
+        for cells in ["r1c1 foo", "r1c1 r1c10", "r1c1 r1c3", "r1c1 r1c2 r1c1"] {
+            let input = format!("lines:\nwhisper: {}", cells);
+            assert!(matches!(
+                parse_lines(&input, "whisper", 9),
+                Err(ParseError::InvalidSection { .. })
+            ));
+        }
+
+        // Rows without a kind or with an unknown kind are invalid for every kind
+        for row in ["r1c1 r1c2", "thermo: r1c1 r1c2"] {
+            let input = format!("lines:\n{}", row);
+            assert!(matches!(
+                parse_lines(&input, "renban", 9),
+                Err(ParseError::InvalidSection { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{
+    all_numbers, candidates, invalid_section, parse_position, reachable_sums, section_content,
+    SudokuRule,
+};
+
+/// A clue outside of the field with the sum of the numbers on its diagonal
+#[derive(Debug, Clone)]
+struct Clue {
+    cells: Vec<(usize, usize)>,
+    sum: i32,
+    /// Step from one cell of the diagonal to the next, like the cells are indexed
+    direction: (isize, isize),
+}
+
+/// Little Killer Sudoku
+///
+/// Every clue has its own line with the position outside of the field,
+/// the direction of the diagonal and its sum. Numbers can repeat on a diagonal.
+/// The position uses line 0 or 10 and column 0 or 10 for cells outside of the field
+/// (0 or the size of the Sudoku + 1 for other sizes).
+/// The direction is one of `dr` (down right), `dl`, `ur` and `ul`:
+/// ```text
+/// little-killer:
+/// r0c2 dr 15
+/// r10c5 ur 22;
+/// ```
+#[derive(Debug, Clone)]
+pub struct LittleKillerSudoku {
+    clues: Vec<Clue>,
+}
+
+impl SudokuRule for LittleKillerSudoku {
+    fn str_identifier() -> &'static str {
+        "little-killer"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let mut clues = vec![];
+        for line in section_content(input, Self::str_identifier()).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            clues.push(Self::parse_clue(line, geometry.size()).ok_or_else(|| {
+                invalid_section(Self::str_identifier(), format!("Invalid clue {}", line))
+            })?);
+        }
+
+        Ok(Box::new(Self { clues }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for clue in &self.clues {
+            let mut sum = 0;
+            let mut unset = 0;
+            for &(row, collum) in &clue.cells {
+                if field[row][collum].count_ones() != 1 {
+                    unset += 1;
+                    continue;
+                }
+                sum += field[row][collum].trailing_zeros() as i32 + 1;
+            }
+
+            if sum + unset > clue.sum || sum + field.len() as i32 * unset < clue.sum {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let mut possible = all_numbers(field);
+
+        for clue in &self.clues {
+            if !clue.cells.contains(&(row, collum)) {
+                continue;
+            }
+
+            let others = reachable_sums(
+                clue.cells
+                    .iter()
+                    .filter(|&&cell| cell != (row, collum))
+                    .map(|&(r, c)| candidates(field, r, c)),
+            );
+
+            let mut possible_on_diagonal = 0;
+            for num in 1..=field.len() as i32 {
+                if others.contains(clue.sum - num) {
+                    possible_on_diagonal |= 0b1 << (num - 1);
+                }
+            }
+            possible &= possible_on_diagonal;
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    /// The sum is written outside of the field with an arrow along the diagonal
+    fn draw(&self, drawing: &mut Drawing) {
+        for clue in &self.clues {
+            let (dx, dy) = (clue.direction.0 as f64, clue.direction.1 as f64);
+            let (x, y) = Drawing::center(clue.cells[0]);
+            let (x, y) = (x - dx, y - dy);
+
+            drawing.text((x - dx * 0.1, y - dy * 0.1), &clue.sum.to_string(), 0.35, "#000000");
+            drawing.arrow(&[(x + dx * 0.2, y + dy * 0.2), (x + dx * 0.4, y + dy * 0.4)], "#000000", 0.03);
+        }
+    }
+}
+
+impl LittleKillerSudoku {
+    /// Parses a clue like `r0c2 dr 15`
+    fn parse_clue(input: &str, size: usize) -> Option<Clue> {
+        let mut parts = input.split_whitespace();
+        let (line, column) = parse_position(parts.next()?)?;
+        let (line_step, column_step) = match parts.next()? {
+            "dr" => (1, 1),
+            "dl" => (1, -1),
+            "ur" => (-1, 1),
+            "ul" => (-1, -1),
+            _ => return None,
+        };
+        let sum: i32 = parts.next()?.parse().ok()?;
+
+        let outside = line == 0 || line == size + 1 || column == 0 || column == size + 1;
+        if !outside || line > size + 1 || column > size + 1 || parts.next().is_some() {
+            return None;
+        }
+
+        let mut cells = vec![];
+        let mut line = line.checked_add_signed(line_step);
+        let mut column = column.checked_add_signed(column_step);
+        while let (Some(l), Some(c)) = (line, column) {
+            if !(1..=size).contains(&l) || !(1..=size).contains(&c) {
+                break;
+            }
+
+            cells.push((c - 1, l - 1));
+            line = l.checked_add_signed(line_step);
+            column = c.checked_add_signed(column_step);
+        }
+
+        // Every cell of the diagonal has a number between 1 and the size
+        let sums = cells.len() as i32..=(cells.len() * size) as i32;
+        if cells.is_empty() || !sums.contains(&sum) {
+            return None;
+        }
+        Some(Clue {
+            cells,
+            sum,
+            direction: (column_step, line_step),
+        })
+    }
+}
+
+#[cfg(test)]
+mod little_killer_sudoku_test {
+    use super::LittleKillerSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn parse() {
+        let little_killer = LittleKillerSudoku::from_str("little-killer:\nr0c7 dr 12\nr10c0 ur 45", Geometry::CLASSIC);
+
+        assert_eq!(little_killer.clues[0].cells, vec![(7, 0), (8, 1)]);
+        assert_eq!(little_killer.clues[1].cells.len(), 9);
+        assert_eq!(little_killer.clues[1].cells[0], (0, 8));
+        assert_eq!(
+            LittleKillerSudoku::parse_clue("r5c5 dr 12", 9).map(|c| c.sum),
+            None
+        );
+
+        // The sum has to be possible on the diagonal
+        assert!(LittleKillerSudoku::parse_clue("r0c7 dr 18", 9).is_some());
+        assert!(LittleKillerSudoku::parse_clue("r0c7 dr 19", 9).is_none());
+        assert!(LittleKillerSudoku::parse_clue("r0c7 dr 1", 9).is_none());
+        assert!(LittleKillerSudoku::parse_clue("r0c7 dr -5", 9).is_none());
+        assert!(LittleKillerSudoku::parse_clue("r0c7 dr 200", 9).is_none());
+    }
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        let little_killer = LittleKillerSudoku::from_str("little-killer:\nr0c7 dr 12", Geometry::CLASSIC);
+
+        assert!(little_killer.complys(&field));
+
+        field[7][0] = 0b100;
+        assert!(little_killer.complys(&field));
+
+        field[8][1] = 0b100_000_000;
+        assert!(little_killer.complys(&field));
+
+        field[8][1] = 0b10_000_000;
+        assert!(!little_killer.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let mut field = vec![vec![0; 9]; 9];
+        let little_killer = LittleKillerSudoku::from_str("little-killer:\nr0c7 dr 12", Geometry::CLASSIC);
+
+        // 12 needs at least a 3 in both cells
+        assert_eq!(
+            little_killer.get_not_possible_numbers_raw(&field, 7, 0),
+            0b11
+        );
+
+        field[8][1] = 0b1_000;
+        assert_eq!(
+            little_killer.get_not_possible_numbers_raw(&field, 7, 0),
+            0b111_111_111 & !0b10_000_000
+        );
+    }
+}
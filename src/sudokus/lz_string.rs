@@ -0,0 +1,105 @@
+//! Decompression of [lz-string](https://github.com/pieroxy/lz-string), that is used by
+//! f-puzzles and SudokuPad to share puzzles in links.
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
+
+/// Reads the bits of the input. Every character of the input has 6 bits, the highest comes first.
+struct Bits {
+    values: Vec<u32>,
+    index: usize,
+    position: u32,
+}
+
+impl Bits {
+    const RESET: u32 = 32;
+
+    fn read(&mut self, count: u32) -> Option<u32> {
+        let mut bits = 0;
+        for power in 0..count {
+            let value = *self.values.get(self.index)?;
+            if value & self.position != 0 {
+                bits |= 0b1 << power;
+            }
+
+            self.position >>= 1;
+            if self.position == 0 {
+                self.position = Self::RESET;
+                self.index += 1;
+            }
+        }
+        Some(bits)
+    }
+}
+
+/// Returns the text of `LZString.compressToBase64` or `None` if the input is invalid
+pub(crate) fn decompress_from_base64(input: &str) -> Option<String> {
+    let values = input
+        .bytes()
+        .map(|c| {
+            BASE64
+                .iter()
+                .position(|&b| b == c)
+                .map(|value| value as u32)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let mut bits = Bits {
+        values,
+        index: 0,
+        position: Bits::RESET,
+    };
+
+    // The first three entries are the commands for an 8 bit character, a 16 bit character and the end
+    let mut dictionary: Vec<Vec<u16>> = vec![vec![], vec![], vec![]];
+    let mut enlarge_in = 4;
+    let mut num_bits = 3;
+
+    let first = match bits.read(2)? {
+        0 => bits.read(8)?,
+        1 => bits.read(16)?,
+        _ => return Some(String::new()),
+    };
+    let mut last = vec![first as u16];
+    dictionary.push(last.clone());
+    let mut result = last.clone();
+
+    loop {
+        let mut code = bits.read(num_bits)? as usize;
+        match code {
+            0 | 1 => {
+                let character = bits.read(if code == 0 { 8 } else { 16 })?;
+                dictionary.push(vec![character as u16]);
+                code = dictionary.len() - 1;
+                enlarge_in -= 1;
+            }
+            2 => return String::from_utf16(&result).ok(),
+            _ => {}
+        }
+
+        if enlarge_in == 0 {
+            enlarge_in = 0b1 << num_bits;
+            num_bits += 1;
+        }
+
+        let entry = match dictionary.get(code) {
+            Some(entry) => entry.clone(),
+            None if code == dictionary.len() => {
+                let mut entry = last.clone();
+                entry.push(last[0]);
+                entry
+            }
+            None => return None,
+        };
+        result.extend_from_slice(&entry);
+
+        let mut new_entry = last;
+        new_entry.push(entry[0]);
+        dictionary.push(new_entry);
+        enlarge_in -= 1;
+        last = entry;
+
+        if enlarge_in == 0 {
+            enlarge_in = 0b1 << num_bits;
+            num_bits += 1;
+        }
+    }
+}
@@ -1,9 +1,50 @@
 pub mod sudoku;
 pub mod fast_sudoku;
+pub mod multi_sudoku;
+pub mod geometry;
+pub mod rule_registry;
+pub mod parse_error;
+pub mod file_format;
+pub mod drawing;
+pub mod booklet;
+pub mod pretty;
 mod x_sudoku;
 mod sudoku_rule;
 mod even_odd_sudoku;
+mod arrow_sudoku;
+mod sandwich_sudoku;
+mod edge_marks;
+mod kropki_sudoku;
+mod xv_sudoku;
+mod offset_sudoku;
+mod non_consecutive_sudoku;
+mod lines;
+mod german_whispers_sudoku;
+mod renban_sudoku;
+mod palindrome_sudoku;
+mod little_killer_sudoku;
+mod skyscraper_sudoku;
+mod disjoint_groups_sudoku;
+mod extra_regions_sudoku;
+mod killer_sudoku;
+mod thermo_sudoku;
+mod line_format;
+mod pencil_marks;
+mod lz_string;
+mod f_puzzles;
+mod picture;
+mod digits;
+#[cfg(feature = "serde")]
+mod serialization;
 
 pub use sudoku::Sudoku;
 pub use fast_sudoku::FastSudoku;
-use sudoku_rule::SudokuRule;
+pub use multi_sudoku::MultiSudoku;
+pub use geometry::Geometry;
+pub use rule_registry::{RuleConstructor, RuleRegistry};
+pub use parse_error::ParseError;
+pub use file_format::FileFormat;
+pub use drawing::Drawing;
+pub use booklet::Booklet;
+pub use pretty::Pretty;
+pub use sudoku_rule::SudokuRule;
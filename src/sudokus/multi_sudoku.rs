@@ -0,0 +1,449 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::rule_registry::RuleRegistry;
+use super::sudoku::Sudoku;
+use super::sudoku_rule::{candidates, parse_position};
+
+/// A cell of a grid as (grid, row, collum)
+type GridCell = (usize, usize, usize);
+
+/// A Sudoku that is placed somewhere in the combined layout
+struct Grid {
+    /// Line and column of the upper left cell in the combined layout (starting at 0)
+    offset: (usize, usize),
+    sudoku: Sudoku,
+}
+
+impl Grid {
+    fn size(&self) -> usize {
+        self.sudoku.geometry().size()
+    }
+}
+
+/// Multiple Sudokus that share some of their cells, like a Samurai Sudoku.
+///
+/// Every grid starts with `grid:` and the position of its upper left cell in the
+/// combined layout. It is followed by the sections of a normal Sudoku, so every grid
+/// can have its own rules. A Samurai Sudoku looks like this:
+/// ```text
+/// grid: r1c1
+/// field: ...;
+///
+/// grid: r1c13
+/// field: ...;
+///
+/// grid: r7c7
+/// field: ...;
+///
+/// grid: r13c1
+/// field: ...;
+///
+/// grid: r13c13
+/// field: ...;
+/// ```
+pub struct MultiSudoku {
+    grids: Vec<Grid>,
+
+    /// Cells that are part of more than one grid. Every entry contains all copies of a cell.
+    shared: Vec<Vec<GridCell>>,
+}
+
+impl PartialEq for MultiSudoku {
+    fn eq(&self, other: &Self) -> bool {
+        self.grids.len() == other.grids.len()
+            && self
+                .grids
+                .iter()
+                .zip(&other.grids)
+                .all(|(grid, other)| grid.offset == other.offset && grid.sudoku == other.sudoku)
+    }
+}
+
+impl fmt::Display for MultiSudoku {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (lines, columns) = self.size();
+        // The boxes of the first grid are used to space the combined layout
+        let geometry = self.grids.first().map(|grid| grid.sudoku.geometry()).unwrap_or_default();
+
+        for line in 0..lines {
+            for column in 0..columns {
+                match self.get_number(line, column) {
+                    Some(number) => write!(f, "{} ", Geometry::number_to_char(number))?,
+                    None => write!(f, "  ")?,
+                }
+                if column % geometry.box_width() == geometry.box_width() - 1 {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+            if line % geometry.box_height() == geometry.box_height() - 1 {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for MultiSudoku {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl MultiSudoku {
+    /// Relative Path of multi grid Sudokus with their solution.
+    /// For the format of the name see `Sudoku::UNSOLVED_SUDOKU_FILES`
+    pub const SUDOKUS_WITH_SOLUTION: [[&'static str; 2]; 1] = [
+        // Samurai-Sudoku
+        [
+            "test-sudokus/text/constructed-26-10-18-samurai-hard-unsolved",
+            "test-sudokus/text/constructed-26-10-18-samurai-hard-solved",
+        ],
+    ];
+
+    /// Returns a multi grid Sudoku from File
+    /// ```
+    /// use sudoku::sudokus::MultiSudoku;
+    ///
+    /// let sudoku = MultiSudoku::from_file("test-sudokus/text/constructed-26-10-18-samurai-hard-unsolved");
+    /// ```
+    pub fn from_file(file_path: &str) -> Self {
+        MultiSudoku::parse(&fs::read_to_string(file_path).expect("todo"))
+    }
+
+    /// Returns a multi grid Sudoku from File with the rules of the registry
+    /// ```
+    /// use sudoku::sudokus::{MultiSudoku, RuleRegistry};
+    ///
+    /// let path = "test-sudokus/text/constructed-26-10-18-samurai-hard-unsolved";
+    /// assert!(MultiSudoku::from_file_with_registry(path, &RuleRegistry::default()).is_ok());
+    /// assert!(MultiSudoku::from_file_with_registry("test-sudokus/text/missing", &RuleRegistry::default()).is_err());
+    /// ```
+    pub fn from_file_with_registry(file_path: &str, registry: &RuleRegistry) -> Result<Self, ParseError> {
+        let content = fs::read_to_string(file_path).map_err(|error| ParseError::Io(error.to_string()))?;
+        MultiSudoku::parse_with_registry(&content, registry)
+    }
+
+    /// Returns a multi grid Sudoku from the content of a file
+    /// ```
+    /// use sudoku::sudokus::MultiSudoku;
+    ///
+    /// let sudoku = MultiSudoku::parse("grid: r1c1\nfield: 1; grid: r4c4\nfield: 2;");
+    /// assert_eq!(sudoku.get_number(0, 0), Some(1));
+    /// assert_eq!(sudoku.get_number(3, 3), Some(2));
+    /// assert_eq!(sudoku.get_number(0, 12), None);
+    /// ```
+    pub fn parse(content: &str) -> Self {
+        // ToDo: Return Result and not just panic if their is an error while parsing
+        MultiSudoku::parse_with_registry(content, &RuleRegistry::default()).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Returns a multi grid Sudoku from the content of a file with the rules of the registry
+    pub fn parse_with_registry(content: &str, registry: &RuleRegistry) -> Result<Self, ParseError> {
+        let mut grids = vec![];
+
+        for data in content.split("grid:").skip(1) {
+            let data = data.trim_start();
+            let (position, data) = data.split_once(char::is_whitespace).unwrap_or((data, ""));
+
+            let (line, column) = parse_position(position)
+                .filter(|&(line, column)| line >= 1 && column >= 1)
+                .ok_or_else(|| ParseError::InvalidSection {
+                    identifier: "grid".to_string(),
+                    message: format!("invalid position {}", position),
+                })?;
+
+            grids.push(Grid {
+                offset: (line - 1, column - 1),
+                sudoku: Sudoku::parse_with_registry(data, registry)?,
+            });
+        }
+
+        // Cells of different grids at the same position are the same cell
+        let mut cells: BTreeMap<(usize, usize), Vec<GridCell>> = BTreeMap::new();
+        for (index, grid) in grids.iter().enumerate() {
+            let size = grid.sudoku.geometry().size();
+            for row in 0..size {
+                for collum in 0..size {
+                    cells
+                        .entry((grid.offset.0 + collum, grid.offset.1 + row))
+                        .or_default()
+                        .push((index, row, collum));
+                }
+            }
+        }
+        let shared: Vec<Vec<GridCell>> = cells
+            .into_values()
+            .filter(|copies| copies.len() > 1)
+            .collect();
+
+        // Overlapping grids have to share their boxes
+        for copies in &shared {
+            let geometry = grids[copies[0].0].sudoku.geometry();
+            if copies.iter().any(|&(grid, _, _)| grids[grid].sudoku.geometry() != geometry) {
+                return Err(ParseError::InvalidSection {
+                    identifier: "grid".to_string(),
+                    message: "overlapping grids need the same size and boxes".to_string(),
+                });
+            }
+        }
+
+        Ok(Self { grids, shared })
+    }
+
+    /// Returns the number of lines and columns of the combined layout
+    pub fn size(&self) -> (usize, usize) {
+        let lines = self.grids.iter().map(|grid| grid.offset.0 + grid.size()).max();
+        let columns = self.grids.iter().map(|grid| grid.offset.1 + grid.size()).max();
+
+        (lines.unwrap_or(0), columns.unwrap_or(0))
+    }
+
+    /// Returns the number of a cell in the combined layout.
+    /// If the cell isn't part of a grid it returns None.
+    /// If no or multible numbers are set in all grids it returns 0.
+    pub fn get_number(&self, line: usize, column: usize) -> Option<i32> {
+        self.grids
+            .iter()
+            .filter_map(|grid| {
+                let collum = line
+                    .checked_sub(grid.offset.0)
+                    .filter(|&collum| collum < grid.size())?;
+                let row = column.checked_sub(grid.offset.1).filter(|&row| row < grid.size())?;
+
+                Some(grid.sudoku.get_number(row, collum))
+            })
+            .reduce(|number, other| if number == 0 { other } else { number })
+    }
+
+    /// Returns the grids of the puzzle
+    pub fn sudokus(&self) -> impl Iterator<Item = &Sudoku> {
+        self.grids.iter().map(|grid| &grid.sudoku)
+    }
+
+    /// Returns true if all grids are correctly solved
+    /// ```
+    /// use sudoku::sudokus::MultiSudoku;
+    ///
+    /// let sudoku = MultiSudoku::from_file("test-sudokus/text/constructed-26-10-18-samurai-hard-solved");
+    /// assert!(sudoku.is_correct());
+    /// ```
+    pub fn is_correct(&self) -> bool {
+        self.grids.iter().all(|grid| grid.sudoku.is_correct()) && self.is_shared_correct()
+    }
+
+    /// Returns true, if there are no errors
+    pub fn is_part_correct(&self) -> bool {
+        self.grids.iter().all(|grid| grid.sudoku.is_part_correct()) && self.is_shared_correct()
+    }
+
+    /// Returns true if all set copies of a shared cell have the same number
+    fn is_shared_correct(&self) -> bool {
+        self.shared.iter().all(|copies| {
+            let mut numbers = copies
+                .iter()
+                .map(|&(grid, row, collum)| &self.grids[grid].sudoku.field[row][collum])
+                .filter(|number| number.count_ones() == 1);
+
+            match numbers.next() {
+                Some(first) => numbers.all(|number| number == first),
+                None => true,
+            }
+        })
+    }
+
+    /// Solves all grids together and returns true if it is solvable
+    /// ```
+    /// use sudoku::sudokus::MultiSudoku;
+    ///
+    /// let mut sudoku = MultiSudoku::from_file("test-sudokus/text/constructed-26-10-18-samurai-hard-unsolved");
+    /// sudoku.solve();
+    /// assert!(sudoku.is_correct());
+    /// ```
+    pub fn solve(&mut self) -> bool {
+        loop {
+            if !self.share_candidates() || !self.is_part_correct() {
+                return false;
+            }
+            if self.is_correct() {
+                return true;
+            }
+            if self.grids.iter().any(|grid| !grid.sudoku.is_solveable()) {
+                return false;
+            }
+
+            let mut number_set = false;
+            for grid in &mut self.grids {
+                number_set |= grid.sudoku.set_missing_numbers();
+            }
+            if number_set {
+                continue;
+            }
+
+            if !self
+                .grids
+                .iter_mut()
+                .any(|grid| grid.sudoku.set_alone_number())
+            {
+                return self.brute_force();
+            }
+        }
+    }
+
+    /// Restricts every copy of a shared cell to the numbers that are possible in all grids.
+    /// It returns false, if no number is left for a cell.
+    fn share_candidates(&mut self) -> bool {
+        for copies in &self.shared {
+            let mut possible = u32::MAX;
+            for &(grid, row, collum) in copies {
+                let numbers = self.grids[grid].sudoku.field[row][collum];
+                // An empty cell hasn't been looked at by the solver yet
+                if numbers != 0 {
+                    possible &= numbers;
+                }
+            }
+
+            if possible == 0 {
+                return false;
+            }
+            if possible == u32::MAX {
+                continue;
+            }
+
+            for &(grid, row, collum) in copies {
+                self.grids[grid].sudoku.field[row][collum] = possible;
+            }
+        }
+
+        true
+    }
+
+    /// Brutforces the cell with the fewest possible numbers.
+    /// It returns true, if the Sudoku is solved afterwards.
+    fn brute_force(&mut self) -> bool {
+        let mut to_check = None;
+        let mut fewest = u32::MAX;
+        for (index, grid) in self.grids.iter().enumerate() {
+            for row in 0..grid.size() {
+                for collum in 0..grid.size() {
+                    if grid.sudoku.is_set(row, collum) {
+                        continue;
+                    }
+
+                    let possible = grid.sudoku.geometry().all()
+                        & !grid.sudoku.get_not_possible_numbers_raw(row, collum)
+                        & candidates(&grid.sudoku.field, row, collum);
+                    if possible.count_ones() < fewest {
+                        fewest = possible.count_ones();
+                        to_check = Some((index, row, collum, possible));
+                    }
+                }
+            }
+        }
+        let Some((index, row, collum, possible)) = to_check else {
+            return false;
+        };
+
+        let fields: Vec<_> = self.grids.iter().map(|grid| grid.sudoku.field.clone()).collect();
+        let moves: Vec<_> = self.grids.iter().map(|grid| grid.sudoku.moves.clone()).collect();
+
+        for num in 0..u32::BITS {
+            if possible & (0b1 << num) == 0 {
+                continue;
+            }
+            self.grids[index].sudoku.field[row][collum] = 0b1 << num;
+
+            if self.solve() {
+                self.grids[index].sudoku.moves.bruteforce_moves += 1;
+                return true;
+            }
+
+            // The moves of the failed guess are counted as failed and the grids are reset
+            for ((grid, field), before) in self.grids.iter_mut().zip(&fields).zip(&moves) {
+                let moves = &mut grid.sudoku.moves;
+                moves.failed_alone_moves += moves.alone_moves - before.alone_moves;
+                moves.failed_missing_moves += moves.missing_moves - before.missing_moves;
+                moves.missing_moves = before.missing_moves;
+                moves.alone_moves = before.alone_moves;
+                grid.sudoku.field.clone_from(field);
+            }
+            self.grids[index].sudoku.moves.bruteforce_failed_tries += 1;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod multi_sudoku_test {
+    use super::MultiSudoku;
+    use crate::sudokus::{ParseError, RuleRegistry};
+
+    #[test]
+    fn shared_cells() {
+        // The lower right box of the first grid is the upper left box of the second grid
+        let sudoku = MultiSudoku::parse("grid: r1c1\nfield: 0;\ngrid: r7c7\nfield: 0;");
+
+        assert_eq!(sudoku.size(), (15, 15));
+        assert_eq!(sudoku.shared.len(), 9);
+        assert!(sudoku.shared.contains(&vec![(0, 6, 6), (1, 0, 0)]));
+        assert!(sudoku.shared.contains(&vec![(0, 8, 6), (1, 2, 0)]));
+    }
+
+    #[test]
+    fn share_candidates() {
+        let mut sudoku = MultiSudoku::parse("grid: r1c1\nfield: 0;\ngrid: r7c7\nfield: 0;");
+        sudoku.grids[0].sudoku.field[6][6] = 0b111;
+        sudoku.grids[1].sudoku.field[0][0] = 0b110;
+
+        assert!(sudoku.share_candidates());
+        assert_eq!(sudoku.grids[0].sudoku.field[6][6], 0b110);
+        assert_eq!(sudoku.get_number(6, 6), Some(0));
+
+        sudoku.grids[1].sudoku.field[0][0] = 0b1;
+        assert!(!sudoku.share_candidates());
+    }
+
+    #[test]
+    fn shared_correct() {
+        let mut sudoku = MultiSudoku::parse("grid: r1c1\nfield: 0;\ngrid: r7c7\nfield: 0;");
+        sudoku.grids[0].sudoku.field[8][8] = 0b1;
+        assert!(sudoku.is_part_correct());
+
+        sudoku.grids[1].sudoku.field[2][2] = 0b10;
+        assert!(!sudoku.is_part_correct());
+    }
+
+    #[test]
+    fn invalid() {
+        let registry = RuleRegistry::default();
+        for content in ["grid: foo\nfield: 0;", "grid: r0c1\nfield: 0;"] {
+            assert!(matches!(
+                MultiSudoku::parse_with_registry(content, &registry),
+                Err(ParseError::InvalidSection { .. })
+            ));
+        }
+
+        // Overlapping grids need the same geometry, grids that don't overlap can differ
+        let overlapping = "grid: r1c1\nfield: 0;\ngrid: r4c4\nsize: 4; field: 0;";
+        assert!(matches!(
+            MultiSudoku::parse_with_registry(overlapping, &registry),
+            Err(ParseError::InvalidSection { .. })
+        ));
+        let apart = "grid: r1c1\nfield: 0;\ngrid: r10c10\nsize: 4; field: 0;";
+        assert!(MultiSudoku::parse_with_registry(apart, &registry).is_ok());
+        assert!(matches!(
+            MultiSudoku::parse_with_registry("grid: r1c1\nfoo: 1;", &registry),
+            Err(ParseError::UnknownSection(_))
+        ));
+        assert!(matches!(
+            MultiSudoku::from_file_with_registry("test-sudokus/text/missing", &registry),
+            Err(ParseError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,91 @@
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{all_numbers, marker_section, orthogonal_neighbours, SudokuRule};
+
+/// Non-Consecutive Sudoku
+///
+/// Orthogonally adjacent cells can't have consecutive numbers.
+/// It is enabled with the marker `non-consecutive;`
+#[derive(Debug, Clone)]
+pub struct NonConsecutiveSudoku {}
+
+impl SudokuRule for NonConsecutiveSudoku {
+    fn str_identifier() -> &'static str {
+        "non-consecutive"
+    }
+
+    fn try_from_str(input: &str, _geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        marker_section(input, Self::str_identifier())?;
+        Ok(Box::new(Self {}))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for row in 0..field.len() {
+            for collum in 0..field.len() {
+                if field[row][collum].count_ones() != 1 {
+                    continue;
+                }
+
+                for (other_row, other_collum) in orthogonal_neighbours(row, collum, field.len()) {
+                    if field[other_row][other_collum].count_ones() == 1
+                        && (field[other_row][other_collum] == field[row][collum] << 1
+                            || field[other_row][other_collum] == field[row][collum] >> 1)
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let mut consecutive = 0;
+        for (other_row, other_collum) in orthogonal_neighbours(row, collum, field.len()) {
+            if field[other_row][other_collum].count_ones() == 1 {
+                consecutive |= field[other_row][other_collum] << 1 | field[other_row][other_collum] >> 1;
+            }
+        }
+
+        all_numbers(field) & consecutive
+    }
+}
+
+#[cfg(test)]
+mod non_consecutive_sudoku_test {
+    use super::NonConsecutiveSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b1;
+        field[1][0] = 0b100;
+        field[1][1] = 0b10_000;
+
+        let non_consecutive = NonConsecutiveSudoku::from_str("", Geometry::CLASSIC);
+        assert!(non_consecutive.complys(&field));
+
+        field[0][1] = 0b10;
+        assert!(!non_consecutive.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[4][4] = 0b10;
+        field[4][6] = 0b100_000_000;
+
+        let non_consecutive = NonConsecutiveSudoku::from_str("", Geometry::CLASSIC);
+        assert_eq!(non_consecutive.get_not_possible_numbers_raw(&field, 4, 3), 0b101);
+        assert_eq!(non_consecutive.get_not_possible_numbers_raw(&field, 4, 5), 0b010_000_101);
+        assert_eq!(non_consecutive.get_not_possible_numbers_raw(&field, 3, 3), 0);
+    }
+}
@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{marker_section, offset_cells, SudokuRule};
+
+/// The cells a chess piece can reach in a single move
+pub trait Piece {
+    /// Identifier of the marker that enables the rule
+    const IDENTIFIER: &'static str;
+
+    /// Offsets of all moves
+    const MOVES: &'static [(isize, isize)];
+}
+
+/// The moves of a king
+#[derive(Debug, Clone)]
+pub struct King {}
+
+impl Piece for King {
+    const IDENTIFIER: &'static str = "anti-king";
+    const MOVES: &'static [(isize, isize)] = &[
+        (1, 1),
+        (1, 0),
+        (1, -1),
+        (0, 1),
+        (0, -1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+}
+
+/// The moves of a knight
+#[derive(Debug, Clone)]
+pub struct Knight {}
+
+impl Piece for Knight {
+    const IDENTIFIER: &'static str = "anti-knight";
+    const MOVES: &'static [(isize, isize)] = &[
+        (1, 2),
+        (2, 1),
+        (-1, 2),
+        (-2, 1),
+        (1, -2),
+        (2, -1),
+        (-1, -2),
+        (-2, -1),
+    ];
+}
+
+/// Cells that are a move of a chess piece apart can't have the same number.
+/// It is enabled with the marker of the piece, like `anti-knight;`
+#[derive(Debug, Clone)]
+pub struct OffsetSudoku<P: Piece> {
+    piece: PhantomData<P>,
+}
+
+/// Anti-King Sudoku: cells that are a king's move apart can't have the same number
+pub type AntiKingSudoku = OffsetSudoku<King>;
+
+/// Anti-Knight Sudoku: cells that are a knight's move apart can't have the same number
+pub type AntiKnightSudoku = OffsetSudoku<Knight>;
+
+impl<P: Piece> SudokuRule for OffsetSudoku<P> {
+    fn str_identifier() -> &'static str {
+        P::IDENTIFIER
+    }
+
+    fn try_from_str(input: &str, _geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        marker_section(input, Self::str_identifier())?;
+        Ok(Box::new(Self { piece: PhantomData }))
+    }
+
+    /// The cells a move apart are peers, so the Sudoku already checks them
+    fn complys(&self, _field: &[Vec<u32>]) -> bool {
+        true
+    }
+
+    fn peers(&self, row: usize, collum: usize, size: usize) -> Vec<(usize, usize)> {
+        offset_cells(row, collum, P::MOVES, size).collect()
+    }
+}
+
+#[cfg(test)]
+mod offset_sudoku_test {
+    use super::{AntiKingSudoku, AntiKnightSudoku};
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::{Geometry, Sudoku};
+
+    #[test]
+    fn comply() {
+        // The cells a move apart are peers and are checked by the Sudoku
+        let mut sudoku = Sudoku::parse("field: 0; anti-knight;");
+        sudoku.field[2][2] = 0b1;
+        sudoku.field[3][3] = 0b1;
+        assert!(sudoku.is_part_correct());
+        sudoku.field[4][3] = 0b1;
+        assert!(!sudoku.is_part_correct());
+
+        let mut sudoku = Sudoku::parse("field: 0; anti-king;");
+        sudoku.field[2][2] = 0b1;
+        sudoku.field[3][3] = 0b1;
+        assert!(AntiKingSudoku::from_str("", Geometry::CLASSIC).complys(&sudoku.field));
+        assert!(!sudoku.is_part_correct());
+    }
+
+    #[test]
+    fn peers() {
+        let anti_king = AntiKingSudoku::from_str("", Geometry::CLASSIC);
+        let peers = anti_king.peers(4, 4, 9);
+        assert_eq!(peers.len(), 8);
+        assert!(peers.contains(&(3, 3)) && peers.contains(&(5, 4)));
+        assert!(!peers.contains(&(2, 3)));
+        assert_eq!(anti_king.peers(8, 8, 9).len(), 3);
+
+        let anti_knight = AntiKnightSudoku::from_str("", Geometry::CLASSIC);
+        let peers = anti_knight.peers(4, 4, 9);
+        assert_eq!(peers.len(), 8);
+        assert!(peers.contains(&(2, 3)) && peers.contains(&(6, 5)));
+        assert!(!peers.contains(&(3, 3)));
+        assert_eq!(anti_knight.peers(0, 0, 9), vec![(1, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(AntiKingSudoku::str_identifier(), "anti-king");
+        assert_eq!(AntiKnightSudoku::str_identifier(), "anti-knight");
+    }
+}
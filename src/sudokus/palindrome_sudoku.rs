@@ -0,0 +1,106 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::lines::{parse_lines, LINES_IDENTIFIER};
+use super::parse_error::ParseError;
+use super::sudoku_rule::{all_numbers, candidates, SudokuRule};
+
+/// Palindrome Sudoku
+///
+/// A line reads the same from both ends. For the data structure see `parse_lines`:
+/// ```text
+/// lines:
+/// palindrome: r7c1 r8c2 r9c3;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PalindromeSudoku {
+    lines: Vec<Vec<(usize, usize)>>,
+}
+
+impl SudokuRule for PalindromeSudoku {
+    fn str_identifier() -> &'static str {
+        "palindrome"
+    }
+
+    fn section_identifier() -> &'static str {
+        LINES_IDENTIFIER
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        Ok(Box::new(Self {
+            lines: parse_lines(input, Self::str_identifier(), geometry.size())?,
+        }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for line in &self.lines {
+            for (&(row, collum), &(other_row, other_collum)) in line.iter().zip(line.iter().rev()) {
+                if field[row][collum].count_ones() == 1
+                    && field[other_row][other_collum].count_ones() == 1
+                    && field[row][collum] != field[other_row][other_collum]
+                {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let mut possible = all_numbers(field);
+
+        for line in &self.lines {
+            for (&cell, &(other_row, other_collum)) in line.iter().zip(line.iter().rev()) {
+                if cell == (row, collum) && cell != (other_row, other_collum) {
+                    possible &= candidates(field, other_row, other_collum);
+                }
+            }
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        for line in &self.lines {
+            drawing.cell_line(line, "#a0a0a0", 0.15);
+        }
+    }
+}
+
+#[cfg(test)]
+mod palindrome_sudoku_test {
+    use super::PalindromeSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b1_000;
+        field[1][1] = 0b1;
+
+        let palindrome = PalindromeSudoku::from_str("lines:\npalindrome: r1c1 r2c2 r3c3", Geometry::CLASSIC);
+        assert!(palindrome.complys(&field));
+
+        field[2][2] = 0b1_000;
+        assert!(palindrome.complys(&field));
+
+        field[2][2] = 0b100;
+        assert!(!palindrome.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b1_000;
+
+        let palindrome = PalindromeSudoku::from_str("lines:\npalindrome: r1c1 r2c2 r3c3", Geometry::CLASSIC);
+        assert_eq!(palindrome.get_not_possible_numbers_raw(&field, 2, 2), 0b111_110_111);
+        assert_eq!(palindrome.get_not_possible_numbers_raw(&field, 1, 1), 0);
+    }
+}
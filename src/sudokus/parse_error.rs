@@ -0,0 +1,59 @@
+use std::fmt;
+
+use super::file_format::FileFormat;
+
+/// Error while reading a Sudoku
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The file couldn't be read
+    Io(String),
+
+    /// No rule is registered for the identifier of a section
+    UnknownSection(String),
+
+    /// A Sudoku in a single line is invalid
+    InvalidLine(String),
+
+    /// A file in the format of another program is invalid
+    InvalidFormat {
+        format: FileFormat,
+        message: String,
+    },
+
+    /// A puzzle of f-puzzles or SudokuPad is invalid
+    InvalidFPuzzles(String),
+
+    /// A picture couldn't be decoded or has no grid
+    InvalidPicture(String),
+
+    /// A constraint of another program has no rule in this crate
+    Unsupported(String),
+
+    /// The content of a section is invalid
+    InvalidSection {
+        identifier: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Io(error) => write!(f, "Unable to read the file: {}", error),
+            ParseError::UnknownSection(identifier) => write!(f, "Unknown section: {}", identifier),
+            ParseError::InvalidLine(message) => write!(f, "Invalid line: {}", message),
+            ParseError::InvalidFormat { format, message } => {
+                write!(f, "Invalid {} file: {}", format, message)
+            }
+            ParseError::InvalidFPuzzles(message) => write!(f, "Invalid f-puzzles puzzle: {}", message),
+            ParseError::InvalidPicture(message) => write!(f, "Invalid picture: {}", message),
+            ParseError::Unsupported(constraint) => write!(f, "Unsupported constraint: {}", constraint),
+            ParseError::InvalidSection {
+                identifier,
+                message,
+            } => write!(f, "Invalid {} section: {}", identifier, message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
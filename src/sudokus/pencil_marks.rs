@@ -0,0 +1,198 @@
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+
+/// Identifier of the section with the candidates of every cell
+pub(crate) const CANDIDATES_IDENTIFIER: &str = "candidates";
+
+/// Characters of the borders between boxes
+const BORDERS: [char; 7] = ['|', ':', '\'', '.', '-', '+', '*'];
+
+/// Returns the candidates of every cell in the raw format, in the same order as the `field` section.
+///
+/// Every cell has all its candidates in one word. Borders like the ones of HoDoKu are ignored.
+/// The numbers of a cell can also be written in braces, like `{1,2,5}`. An empty cell is a `.`:
+/// ```text
+/// .----------------.----------------.----------------.
+/// | 5    3    12   | 6    7    248  | 19   148  29   |
+/// ...
+/// :----------------+----------------+----------------:
+/// ...
+/// '----------------'----------------'----------------'
+/// ```
+pub(crate) fn parse_pencil_marks(input: &str) -> Result<(Geometry, Vec<u32>), ParseError> {
+    let mut cells = vec![];
+    for line in input.lines() {
+        // Lines with only borders separate the boxes
+        if line.contains('-')
+            && line
+                .chars()
+                .all(|c| c.is_whitespace() || BORDERS.contains(&c))
+        {
+            continue;
+        }
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c.is_whitespace() || c == '|' || c == ':' {
+                continue;
+            }
+
+            let mut cell = 0;
+            if c == '{' {
+                let numbers: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                for number in numbers
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|number| !number.is_empty())
+                {
+                    cell |= raw(parse_number(number)?);
+                }
+            } else if c != '.' {
+                cell |= raw(char_to_number(c)?);
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '|' && *c != ':')
+                {
+                    cell |= raw(char_to_number(c)?);
+                }
+            }
+            cells.push(cell);
+        }
+    }
+
+    let geometry = (1..=Geometry::MAX_SIZE)
+        .find(|size| size * size == cells.len())
+        .and_then(Geometry::with_size)
+        .ok_or_else(|| error(format!("{} cells are not a square field", cells.len())))?;
+    if let Some(&cell) = cells.iter().find(|&&cell| cell & !geometry.all() != 0) {
+        return Err(error(format!(
+            "{} is too big for a Sudoku of size {}",
+            cell.ilog2() + 1,
+            geometry.size()
+        )));
+    }
+
+    Ok((geometry, cells))
+}
+
+/// Writes the candidates of every cell in a grid with borders like HoDoKu.
+/// Cells without candidates are written as `.`
+pub(crate) fn write_pencil_marks(geometry: Geometry, cells: &[u32]) -> String {
+    let size = geometry.size();
+    let words: Vec<String> = cells
+        .iter()
+        .map(|&cell| match cell {
+            0 => ".".to_string(),
+            _ => (1..=size as i32)
+                .filter(|number| cell & raw(*number) != 0)
+                .map(Geometry::number_to_char)
+                .collect(),
+        })
+        .collect();
+
+    // Every column is as wide as its widest cell
+    let widths: Vec<usize> = (0..size)
+        .map(|row| {
+            (0..size)
+                .map(|collum| words[collum * size + row].len())
+                .max()
+                .unwrap_or(1)
+        })
+        .collect();
+    let segments: Vec<usize> = widths
+        .chunks(geometry.box_width())
+        .map(|widths| widths.iter().sum::<usize>() + 2 * widths.len())
+        .collect();
+    let border = |left: &str, middle: &str, right: &str| {
+        let lines: Vec<String> = segments.iter().map(|&width| "-".repeat(width)).collect();
+        format!("{}{}{}\n", left, lines.join(middle), right)
+    };
+
+    let mut output = border(".", ".", ".");
+    for collum in 0..size {
+        if collum != 0 && collum % geometry.box_height() == 0 {
+            output.push_str(&border(":", "+", ":"));
+        }
+
+        for rows in (0..size).collect::<Vec<_>>().chunks(geometry.box_width()) {
+            let cells: Vec<String> = rows
+                .iter()
+                .map(|&row| {
+                    format!(
+                        "{:<width$}",
+                        words[collum * size + row],
+                        width = widths[row]
+                    )
+                })
+                .collect();
+            output.push_str(&format!("| {} ", cells.join("  ")));
+        }
+        output.push_str("|\n");
+    }
+    output.push_str(&border("'", "'", "'"));
+
+    output
+}
+
+/// Returns a number in the raw format
+fn raw(number: i32) -> u32 {
+    0b1 << (number - 1)
+}
+
+/// Returns the number of a single character. Unlike in the `field` section `0` isn't a number.
+fn char_to_number(c: char) -> Result<i32, ParseError> {
+    match Geometry::char_to_number(c) {
+        Some(number) if number > 0 => Ok(number),
+        _ => Err(error(format!("Invalid candidate {}", c))),
+    }
+}
+
+/// Returns a number in braces, that can be written with digits or as a letter
+fn parse_number(number: &str) -> Result<i32, ParseError> {
+    match number.parse::<i32>() {
+        Ok(number) if (1..=Geometry::MAX_SIZE as i32).contains(&number) => Ok(number),
+        Ok(_) => Err(error(format!("Invalid candidate {}", number))),
+        Err(_) if number.chars().count() == 1 => char_to_number(number.chars().next().unwrap()),
+        Err(_) => Err(error(format!("Invalid candidate {}", number))),
+    }
+}
+
+fn error(message: String) -> ParseError {
+    ParseError::InvalidSection {
+        identifier: CANDIDATES_IDENTIFIER.to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod pencil_marks_test {
+    use super::{parse_pencil_marks, write_pencil_marks};
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn parse() {
+        let input = ".-------.-------.\n| 12 3  | 4  .  |\n| 4  .  | 12 3  |\n:-------+-------:\n| {1, 3} 2 | 4 {4} |\n| . . | . 1234 |\n'-------'-------'";
+        let (geometry, cells) = parse_pencil_marks(input).unwrap();
+        assert_eq!(geometry, Geometry::new(4, 2, 2).unwrap());
+        assert_eq!(cells[..4], [0b11, 0b100, 0b1000, 0]);
+        assert_eq!(cells[8..12], [0b101, 0b10, 0b1000, 0b1000]);
+        assert_eq!(cells[15], 0b1111);
+
+        assert!(parse_pencil_marks("12 3 4").is_err());
+        assert!(parse_pencil_marks("1 2 3 5").is_err());
+        assert!(parse_pencil_marks("1 2 3 0").is_err());
+    }
+
+    #[test]
+    fn write() {
+        let geometry = Geometry::new(4, 2, 2).unwrap();
+        let mut cells = vec![0b1111; 16];
+        cells[0] = 0b1;
+        cells[1] = 0;
+
+        let output = write_pencil_marks(geometry, &cells);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines[0], ".------------.------------.");
+        assert_eq!(lines[1], "| 1     .    | 1234  1234 |");
+        assert_eq!(lines[3], ":------------+------------:");
+        assert_eq!(parse_pencil_marks(&output).unwrap(), (geometry, cells));
+    }
+}
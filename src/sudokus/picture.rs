@@ -0,0 +1,283 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use super::digits::{self, Glyph, HEIGHT, WIDTH};
+use super::parse_error::ParseError;
+
+/// Number of cells in every row and collum of a picture
+const SIZE: usize = 9;
+
+/// Pixels darker than this are part of a line of the grid
+const LINE: u8 = 200;
+
+/// Pixels darker than this are part of a number
+const INK: u8 = 160;
+
+/// Part of a cell at every side that is skipped, so the lines of the grid aren't part of the number
+const INSET: f64 = 0.12;
+
+/// Cells with a smaller part of dark pixels are empty
+const EMPTY: f64 = 0.02;
+
+/// Samples of every pixel of a glyph in each direction
+const SAMPLES: usize = 4;
+
+/// A picture as the brightness of every pixel, from black (0) to white (255)
+struct Picture {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+/// Returns the numbers of a Sudoku in a PNG picture, in the same order as the `field` section.
+///
+/// The grid is found by its lines and every cell is compared with the digits of `digits`.
+/// Empty cells are `0`.
+pub(crate) fn read_picture(file_path: &str) -> Result<Vec<i32>, ParseError> {
+    let cells = Picture::from_png(file_path)?.cells()?;
+    Ok(cells
+        .iter()
+        .map(|glyph| glyph.as_ref().map_or(0, digits::recognize))
+        .collect())
+}
+
+impl Picture {
+    /// Reads a PNG file. Transparent pixels are on white paper.
+    fn from_png(file_path: &str) -> Result<Self, ParseError> {
+        let file = File::open(file_path).map_err(|error| ParseError::Io(error.to_string()))?;
+        let mut decoder = png::Decoder::new(BufReader::new(file));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder
+            .read_info()
+            .map_err(|error| ParseError::InvalidPicture(error.to_string()))?;
+
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buffer)
+            .map_err(|error| ParseError::InvalidPicture(error.to_string()))?;
+        let samples = info.color_type.samples();
+
+        let pixels = buffer[..info.buffer_size()]
+            .chunks_exact(samples)
+            .map(|pixel| {
+                let (brightness, alpha) = match pixel {
+                    [gray] => (*gray as u32, 255),
+                    [gray, alpha] => (*gray as u32, *alpha as u32),
+                    [red, green, blue] => (luma(*red, *green, *blue), 255),
+                    [red, green, blue, alpha, ..] => (luma(*red, *green, *blue), *alpha as u32),
+                    [] => (255, 255),
+                };
+                (255 - (255 - brightness) * alpha / 255) as u8
+            })
+            .collect();
+
+        Ok(Self {
+            width: info.width as usize,
+            height: info.height as usize,
+            pixels,
+        })
+    }
+
+    /// Returns the number of every cell of the grid, or none if it's empty
+    fn cells(&self) -> Result<Vec<Option<Glyph>>, ParseError> {
+        let (left, width) = fit_grid(&self.lines(true), self.width)
+            .ok_or_else(|| ParseError::InvalidPicture("No grid found".to_string()))?;
+        let (top, height) = fit_grid(&self.lines(false), self.height)
+            .ok_or_else(|| ParseError::InvalidPicture("No grid found".to_string()))?;
+
+        let mut cells = vec![];
+        for collum in 0..SIZE {
+            for row in 0..SIZE {
+                let x = left + row as f64 * width;
+                let y = top + collum as f64 * height;
+                cells.push(self.glyph(
+                    (x + INSET * width) as usize,
+                    (y + INSET * height) as usize,
+                    (x + (1.0 - INSET) * width) as usize,
+                    (y + (1.0 - INSET) * height) as usize,
+                ));
+            }
+        }
+        Ok(cells)
+    }
+
+    fn is_dark(&self, x: usize, y: usize, threshold: u8) -> bool {
+        self.pixels[y * self.width + x] < threshold
+    }
+
+    /// Returns the positions of the vertical (or horizontal) lines, that are longer than half the picture
+    fn lines(&self, vertical: bool) -> Vec<f64> {
+        let (length, across) = match vertical {
+            true => (self.height, self.width),
+            false => (self.width, self.height),
+        };
+        let counts: Vec<usize> = (0..across)
+            .map(|position| {
+                (0..length)
+                    .filter(|&along| match vertical {
+                        true => self.is_dark(position, along, LINE),
+                        false => self.is_dark(along, position, LINE),
+                    })
+                    .count()
+            })
+            .collect();
+
+        // Thick lines are many pixels next to each other
+        let mut lines = vec![];
+        let mut start = None;
+        for (position, &count) in counts.iter().chain([0].iter()).enumerate() {
+            match (count > length / 2, start) {
+                (true, None) => start = Some(position),
+                (false, Some(first)) => {
+                    lines.push((first + position - 1) as f64 / 2.0);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        lines
+    }
+
+    /// Returns the number between the given bounds scaled to the size of the digits,
+    /// or none if the cell is empty
+    fn glyph(&self, left: usize, top: usize, right: usize, bottom: usize) -> Option<Glyph> {
+        let (right, bottom) = (right.min(self.width), bottom.min(self.height));
+        let mut dark = 0;
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (right, left, bottom, top);
+        for y in top..bottom {
+            for x in left..right {
+                if self.is_dark(x, y, INK) {
+                    dark += 1;
+                    (min_x, max_x) = (min_x.min(x), max_x.max(x));
+                    (min_y, max_y) = (min_y.min(y), max_y.max(y));
+                }
+            }
+        }
+        // Cells outside of the picture are empty, too
+        let area = right.saturating_sub(left) * bottom.saturating_sub(top);
+        if dark == 0 || (dark as f64) < EMPTY * area as f64 {
+            return None;
+        }
+
+        // The number keeps its proportions and is centered horizontally
+        let scale = (max_y - min_y + 1) as f64 / HEIGHT as f64;
+        let center = (min_x + max_x + 1) as f64 / 2.0;
+        let mut glyph = [0.0; WIDTH * HEIGHT];
+        for (index, value) in glyph.iter_mut().enumerate() {
+            let mut count = 0;
+            for sample in 0..SAMPLES * SAMPLES {
+                let offset_x = ((sample % SAMPLES) as f64 + 0.5) / SAMPLES as f64;
+                let offset_y = ((sample / SAMPLES) as f64 + 0.5) / SAMPLES as f64;
+                let x = center + ((index % WIDTH) as f64 + offset_x - WIDTH as f64 / 2.0) * scale;
+                let y = min_y as f64 + ((index / WIDTH) as f64 + offset_y) * scale;
+                if x >= 0.0
+                    && (x as usize) < self.width
+                    && (y as usize) < self.height
+                    && self.is_dark(x as usize, y as usize, INK)
+                {
+                    count += 1;
+                }
+            }
+            *value = count as f64 / (SAMPLES * SAMPLES) as f64;
+        }
+        Some(glyph)
+    }
+}
+
+/// Returns the brightness of a color
+fn luma(red: u8, green: u8, blue: u8) -> u32 {
+    (299 * red as u32 + 587 * green as u32 + 114 * blue as u32) / 1000
+}
+
+/// Returns the start and the size of the cells of the grid that fits the found lines best.
+///
+/// Two of the lines are taken as any two lines of the grid, the others have to be at the expected places.
+/// Not every line of the grid has to be found, like the thin lines inside of a box or the lines at the border.
+fn fit_grid(lines: &[f64], length: usize) -> Option<(f64, f64)> {
+    let mut best: Option<(f64, f64, f64)> = None;
+    for (index, &first) in lines.iter().enumerate() {
+        for &second in &lines[index + 1..] {
+            for first_line in 0..SIZE {
+                for second_line in first_line + 1..=SIZE {
+                    let size = (second - first) / (second_line - first_line) as f64;
+                    let start = first - first_line as f64 * size;
+                    let tolerance = size / 8.0;
+                    if size < 8.0
+                        || start < -tolerance
+                        || start + SIZE as f64 * size > length as f64 + tolerance
+                    {
+                        continue;
+                    }
+
+                    // Lines close to the expected places count a bit less
+                    let score: f64 = (0..=SIZE)
+                        .map(|line| {
+                            let expected = start + line as f64 * size;
+                            lines
+                                .iter()
+                                .map(|&line| 1.0 - (line - expected).abs() / tolerance)
+                                .fold(0.0, f64::max)
+                        })
+                        .sum();
+                    if best.is_none_or(|(best, _, _)| score > best) {
+                        best = Some((score, start, size));
+                    }
+                }
+            }
+        }
+    }
+    best.map(|(_, start, size)| (start, size))
+}
+
+#[cfg(test)]
+mod picture_test {
+    use super::{fit_grid, read_picture, Picture};
+    use crate::sudokus::ParseError;
+
+    #[test]
+    fn grid() {
+        // All lines
+        let lines: Vec<f64> = (0..10).map(|line| 10.0 + line as f64 * 40.0).collect();
+        assert_eq!(fit_grid(&lines, 400), Some((10.0, 40.0)));
+
+        // Only the lines of the boxes
+        assert_eq!(
+            fit_grid(&[5.0, 125.0, 245.0, 365.0], 370),
+            Some((5.0, 40.0))
+        );
+
+        // Only the inner lines and a frame with some space around the grid
+        let mut lines: Vec<f64> = (1..9).map(|line| 20.0 + line as f64 * 40.0).collect();
+        lines.insert(0, 10.0);
+        lines.push(390.0);
+        assert_eq!(fit_grid(&lines, 400), Some((20.0, 40.0)));
+
+        assert_eq!(fit_grid(&[10.0], 400), None);
+    }
+
+    #[test]
+    fn glyph() {
+        let picture = Picture {
+            width: 4,
+            height: 4,
+            pixels: vec![0; 16],
+        };
+        assert!(picture.glyph(0, 0, 4, 4).is_some());
+
+        // Bounds outside of the picture
+        assert!(picture.glyph(5, 5, 8, 8).is_none());
+        assert!(picture.glyph(3, 3, 1, 1).is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            read_picture("test-sudokus/text/welt-23-11-11-easy-unsolved"),
+            Err(ParseError::InvalidPicture(_))
+        ));
+        assert!(matches!(
+            read_picture("test-sudokus/pics/missing.png"),
+            Err(ParseError::Io(_))
+        ));
+    }
+}
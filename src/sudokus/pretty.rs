@@ -0,0 +1,262 @@
+use std::fmt;
+
+use super::geometry::Geometry;
+use super::sudoku::Sudoku;
+
+/// Color of the given numbers, bold in the color of the terminal
+const GIVEN: &str = "\x1b[1m";
+
+/// Color of the solved numbers
+const SOLVED: &str = "\x1b[34m";
+
+/// Color of the numbers, that are in conflict with another number
+const CONFLICT: &str = "\x1b[1;31m";
+
+/// Color of the candidates
+const CANDIDATE: &str = "\x1b[90m";
+
+const RESET: &str = "\x1b[0m";
+
+/// Characters of a border from the left to the right: the corner, the border between boxes,
+/// the border between cells, the corner and the line itself
+type Border = [char; 5];
+
+const TOP: Border = ['╔', '╦', '╤', '╗', '═'];
+const BOXES: Border = ['╠', '╬', '╪', '╣', '═'];
+const CELLS: Border = ['╟', '╫', '┼', '╢', '─'];
+const BOTTOM: Border = ['╚', '╩', '╧', '╝', '═'];
+
+/// A Sudoku for the terminal with borders and colors, followed by the statistics of the moves.
+///
+/// The given numbers are bold, solved numbers are blue and numbers in conflict with another
+/// number of their row, collum, box or peers are red. The candidates of the cells can be shown
+/// in a small grid in every cell. It is the same as the alternate format of the Sudoku (`{:#}`).
+/// ```
+/// use sudoku::sudokus::Sudoku;
+///
+/// let sudoku = Sudoku::from_file("test-sudokus/text/welt-23-11-11-easy-unsolved");
+/// let pretty = sudoku.pretty().colors(false).statistics(false).to_string();
+///
+/// assert!(pretty.starts_with("╔═══════╦═══════╦═══════╗\n║ 1 · 3 ║ · · · ║ · · · ║\n"));
+/// assert_eq!(format!("{:#}", sudoku), sudoku.pretty().to_string());
+/// ```
+pub struct Pretty<'a> {
+    sudoku: &'a Sudoku,
+    candidates: bool,
+    colors: bool,
+    statistics: bool,
+}
+
+impl<'a> Pretty<'a> {
+    pub(crate) fn new(sudoku: &'a Sudoku) -> Self {
+        Self {
+            sudoku,
+            candidates: false,
+            colors: true,
+            statistics: true,
+        }
+    }
+
+    /// Shows the candidates of every unset cell in a small grid, like the numbers of a box
+    pub fn candidates(mut self, candidates: bool) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Colors the numbers with ANSI escape codes. Without colors conflicts aren't shown.
+    pub fn colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Writes the number of givens, solved cells and conflicts and the moves after the grid
+    pub fn statistics(mut self, statistics: bool) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// Returns a border between two lines of cells
+    fn border(&self, [left, boxes, cells, right, line]: Border) -> String {
+        let geometry = self.sudoku.geometry();
+        let width = 2 * geometry.box_width() + 1;
+
+        let mut output = left.to_string();
+        for row in 0..geometry.size() {
+            if !self.candidates && row % geometry.box_width() != 0 {
+                continue;
+            }
+            if row != 0 && row % geometry.box_width() == 0 {
+                output.push(boxes);
+            } else if row != 0 {
+                output.push(cells);
+            }
+            output.extend(std::iter::repeat_n(line, width));
+        }
+        output.push(right);
+        output
+    }
+
+    /// Returns the text of a number in its color
+    fn colored(&self, text: char, color: &str) -> String {
+        match self.colors {
+            true => format!("{}{}{}", color, text, RESET),
+            false => text.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Pretty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sudoku = self.sudoku;
+        let geometry = sudoku.geometry();
+        let size = geometry.size();
+        let conflicts = sudoku.conflicts();
+
+        let color = |row: usize, collum: usize| {
+            if conflicts.contains(&(row, collum)) {
+                CONFLICT
+            } else if sudoku.is_given(row, collum) {
+                GIVEN
+            } else {
+                SOLVED
+            }
+        };
+
+        // A cell with candidates has the lines of a box
+        let lines = match self.candidates {
+            true => geometry.box_height(),
+            false => 1,
+        };
+
+        writeln!(f, "{}", self.border(TOP))?;
+        for collum in 0..size {
+            if collum != 0 && collum % geometry.box_height() == 0 {
+                writeln!(f, "{}", self.border(BOXES))?;
+            } else if collum != 0 && self.candidates {
+                writeln!(f, "{}", self.border(CELLS))?;
+            }
+
+            for line in 0..lines {
+                write!(f, "║")?;
+                for row in 0..size {
+                    if row != 0 && row % geometry.box_width() == 0 {
+                        write!(f, " ║")?;
+                    } else if row != 0 && self.candidates {
+                        write!(f, " │")?;
+                    }
+
+                    if !self.candidates {
+                        let text = match sudoku.is_set(row, collum) {
+                            true => self.colored(
+                                Geometry::number_to_char(sudoku.get_number(row, collum)),
+                                color(row, collum),
+                            ),
+                            false => "·".to_string(),
+                        };
+                        write!(f, " {}", text)?;
+                        continue;
+                    }
+
+                    // A set number is in the middle of the cell
+                    for position in 0..geometry.box_width() {
+                        let number = (line * geometry.box_width() + position + 1) as i32;
+                        let text = if sudoku.is_set(row, collum) {
+                            match line == lines / 2 && position == geometry.box_width() / 2 {
+                                true => self.colored(
+                                    Geometry::number_to_char(sudoku.get_number(row, collum)),
+                                    color(row, collum),
+                                ),
+                                false => " ".to_string(),
+                            }
+                        } else if sudoku.field[row][collum] & (0b1 << (number - 1)) != 0 {
+                            self.colored(Geometry::number_to_char(number), CANDIDATE)
+                        } else {
+                            " ".to_string()
+                        };
+                        write!(f, " {}", text)?;
+                    }
+                }
+                writeln!(f, " ║")?;
+            }
+        }
+        writeln!(f, "{}", self.border(BOTTOM))?;
+
+        if self.statistics {
+            let cells: Vec<(usize, usize)> = (0..size * size)
+                .map(|count| (count % size, count / size))
+                .collect();
+            let givens = cells
+                .iter()
+                .filter(|&&(row, collum)| sudoku.is_given(row, collum))
+                .count();
+            let set = cells
+                .iter()
+                .filter(|&&(row, collum)| sudoku.is_set(row, collum))
+                .count();
+
+            writeln!(f)?;
+            writeln!(
+                f,
+                "Givens: {}, solved: {}, unset: {}, conflicts: {}",
+                givens,
+                set - givens,
+                size * size - set,
+                conflicts.len()
+            )?;
+            writeln!(f, "{}", sudoku.moves)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod pretty_test {
+    use crate::sudokus::Sudoku;
+
+    #[test]
+    fn grid() {
+        let mut sudoku = Sudoku::from_line("1..2.2..........").unwrap();
+        let pretty = sudoku.pretty().colors(false).statistics(false).to_string();
+        let lines: Vec<_> = pretty.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "╔═════╦═════╗");
+        assert_eq!(lines[1], "║ 1 · ║ · 2 ║");
+        assert_eq!(lines[3], "╠═════╬═════╣");
+        assert_eq!(lines[6], "╚═════╩═════╝");
+
+        // Givens are bold and solved numbers blue
+        sudoku.solve();
+        let pretty = sudoku.pretty().statistics(false).to_string();
+        assert!(pretty.starts_with("╔═════╦═════╗\n║ \x1b[1m1\x1b[0m \x1b[34m4\x1b[0m ║"));
+    }
+
+    #[test]
+    fn candidates() {
+        let mut sudoku = Sudoku::from_line("1..2.2..........").unwrap();
+        sudoku.set_missing_numbers();
+        let pretty = sudoku
+            .pretty()
+            .candidates(true)
+            .colors(false)
+            .statistics(false)
+            .to_string();
+        let lines: Vec<_> = pretty.lines().collect();
+        assert_eq!(lines[0], "╔═════╤═════╦═════╤═════╗");
+        // A set number is in the middle, the candidates are where they would be in a box
+        assert_eq!(lines[1], "║     │     ║     │     ║");
+        assert_eq!(lines[2], "║   1 │ 3 4 ║ 3 4 │   2 ║");
+        assert_eq!(lines[3], "╟─────┼─────╫─────┼─────╢");
+        assert_eq!(lines[7], "║   2 │ 1   ║ 1 2 │ 1   ║");
+        assert_eq!(lines.len(), 13);
+    }
+
+    #[test]
+    fn conflicts() {
+        let sudoku = Sudoku::from_line("1..1............").unwrap();
+        let pretty = sudoku.pretty().to_string();
+        assert!(
+            pretty.starts_with("╔═════╦═════╗\n║ \x1b[1;31m1\x1b[0m · ║ · \x1b[1;31m1\x1b[0m ║")
+        );
+        assert!(pretty.contains("Givens: 2, solved: 0, unset: 14, conflicts: 2\n"));
+    }
+}
@@ -0,0 +1,157 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::lines::{parse_lines, LINES_IDENTIFIER};
+use super::parse_error::ParseError;
+use super::sudoku_rule::{all_numbers, candidates, invalid_section, SudokuRule};
+
+/// Renban Sudoku
+///
+/// A line contains a set of consecutive numbers in any order without repeats.
+/// For the data structure see `parse_lines`:
+/// ```text
+/// lines:
+/// renban: r4c4 r4c5 r4c6;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RenbanSudoku {
+    lines: Vec<Vec<(usize, usize)>>,
+}
+
+impl SudokuRule for RenbanSudoku {
+    fn str_identifier() -> &'static str {
+        "renban"
+    }
+
+    fn section_identifier() -> &'static str {
+        LINES_IDENTIFIER
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let lines = parse_lines(input, Self::str_identifier(), geometry.size())?;
+
+        if lines.iter().any(|line| line.len() > geometry.size()) {
+            return Err(invalid_section(
+                LINES_IDENTIFIER,
+                "A renban line can't be longer than the size of the Sudoku",
+            ));
+        }
+
+        Ok(Box::new(Self { lines }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for line in &self.lines {
+            let mut set_numbers = 0;
+            for &(row, collum) in line {
+                if field[row][collum].count_ones() != 1 {
+                    continue;
+                }
+                if set_numbers & field[row][collum] != 0 {
+                    return false;
+                }
+
+                set_numbers |= field[row][collum];
+            }
+
+            // The set numbers have to fit in a run of the length of the line
+            if set_numbers != 0
+                && set_numbers.ilog2() - set_numbers.trailing_zeros() >= line.len() as u32
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let mut possible = all_numbers(field);
+
+        for line in &self.lines {
+            if !line.contains(&(row, collum)) {
+                continue;
+            }
+
+            let mut possible_on_line = 0;
+            let run: u32 = (0b1 << line.len()) - 1;
+            for start in 0..=(field.len() - line.len()) {
+                let run = run << start;
+
+                // Every cell needs a number of the run and every number of the run needs a cell
+                if line.iter().any(|&(r, c)| candidates(field, r, c) & run == 0)
+                    || line.iter().fold(0, |all, &(r, c)| all | candidates(field, r, c) & run) != run
+                {
+                    continue;
+                }
+                possible_on_line |= run;
+            }
+
+            for &(other_row, other_collum) in line {
+                if (other_row, other_collum) != (row, collum)
+                    && field[other_row][other_collum].count_ones() == 1
+                {
+                    possible_on_line &= !field[other_row][other_collum];
+                }
+            }
+            possible &= possible_on_line;
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        for line in &self.lines {
+            drawing.cell_line(line, "#c080f0", 0.15);
+        }
+    }
+}
+
+#[cfg(test)]
+mod renban_sudoku_test {
+    use super::RenbanSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b1_000;
+        field[2][1] = 0b10_000;
+
+        let renban = RenbanSudoku::from_str("lines:\nrenban: r1c1 r2c2 r2c3", Geometry::CLASSIC);
+        assert!(renban.complys(&field));
+
+        // 4 _ 6 is still possible, 4 _ 7 isn't
+        field[2][1] = 0b100_000;
+        assert!(renban.complys(&field));
+        field[2][1] = 0b1_000_000;
+        assert!(!renban.complys(&field));
+
+        // Numbers can't repeat
+        field[2][1] = 0b1_000;
+        assert!(!renban.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let mut field = vec![vec![0; 9]; 9];
+        let renban = RenbanSudoku::from_str("lines:\nrenban: r1c1 r2c2 r2c3", Geometry::CLASSIC);
+
+        assert_eq!(renban.get_not_possible_numbers_raw(&field, 1, 1), 0);
+
+        // With a 4 and a 6 only the 5 is left
+        field[0][0] = 0b1_000;
+        field[2][1] = 0b100_000;
+        assert_eq!(renban.get_not_possible_numbers_raw(&field, 1, 1), 0b111_101_111);
+
+        // With a 1 the line is 1 2 3
+        field[0][0] = 0b1;
+        field[2][1] = 0;
+        assert_eq!(renban.get_not_possible_numbers_raw(&field, 1, 1), 0b111_111_001);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::SudokuRule;
+
+use super::arrow_sudoku::ArrowSudoku;
+use super::disjoint_groups_sudoku::DisjointGroupsSudoku;
+use super::even_odd_sudoku::EvenOddSudoku;
+use super::extra_regions_sudoku::ExtraRegionsSudoku;
+use super::german_whispers_sudoku::GermanWhispersSudoku;
+use super::killer_sudoku::KillerSudoku;
+use super::kropki_sudoku::KropkiSudoku;
+use super::little_killer_sudoku::LittleKillerSudoku;
+use super::non_consecutive_sudoku::NonConsecutiveSudoku;
+use super::offset_sudoku::{AntiKingSudoku, AntiKnightSudoku};
+use super::palindrome_sudoku::PalindromeSudoku;
+use super::renban_sudoku::RenbanSudoku;
+use super::sandwich_sudoku::SandwichSudoku;
+use super::skyscraper_sudoku::SkyscraperSudoku;
+use super::thermo_sudoku::ThermoSudoku;
+use super::x_sudoku::XSudoku;
+use super::xv_sudoku::XVSudoku;
+
+/// Creates a rule out of its section
+pub type RuleConstructor = fn(&str, Geometry) -> Result<Box<dyn SudokuRule>, ParseError>;
+
+/// Maps the identifiers of sections to the rules that are read from them.
+///
+/// The default registry knows all rules of this crate. Own rules can be added:
+/// ```
+/// use sudoku::sudokus::{Geometry, ParseError, RuleRegistry, Sudoku, SudokuRule};
+///
+/// /// The upper left cell can't be a 9
+/// struct NoNineInCorner {}
+///
+/// impl SudokuRule for NoNineInCorner {
+///     fn str_identifier() -> &'static str {
+///         "no-nine-in-corner"
+///     }
+///
+///     fn try_from_str(_input: &str, _geometry: Geometry) -> Result<Box<Self>, ParseError> {
+///         Ok(Box::new(Self {}))
+///     }
+///
+///     fn complys(&self, field: &[Vec<u32>]) -> bool {
+///         field[0][0] != 0b100_000_000
+///     }
+///
+///     fn get_not_possible_numbers_raw(&self, _field: &[Vec<u32>], row: usize, collum: usize) -> u32 {
+///         if (row, collum) == (0, 0) { 0b100_000_000 } else { 0 }
+///     }
+/// }
+///
+/// let mut registry = RuleRegistry::default();
+/// registry.register::<NoNineInCorner>();
+///
+/// let content = "field: 0; no-nine-in-corner;";
+/// assert!(Sudoku::parse_with_registry(content, &registry).is_ok());
+/// assert!(Sudoku::parse_with_registry(content, &RuleRegistry::default()).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuleRegistry {
+    sections: HashMap<String, Vec<RuleConstructor>>,
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register::<XSudoku>();
+        registry.register::<EvenOddSudoku>();
+        registry.register::<ArrowSudoku>();
+        registry.register::<SandwichSudoku>();
+        registry.register::<KropkiSudoku>();
+        registry.register::<XVSudoku>();
+        registry.register::<AntiKnightSudoku>();
+        registry.register::<AntiKingSudoku>();
+        registry.register::<NonConsecutiveSudoku>();
+        registry.register::<GermanWhispersSudoku>();
+        registry.register::<RenbanSudoku>();
+        registry.register::<PalindromeSudoku>();
+        registry.register::<LittleKillerSudoku>();
+        registry.register::<SkyscraperSudoku>();
+        registry.register::<DisjointGroupsSudoku>();
+        registry.register::<ExtraRegionsSudoku>();
+        registry.register::<KillerSudoku>();
+        registry.register::<ThermoSudoku>();
+        registry
+    }
+}
+
+impl RuleRegistry {
+    /// Returns a registry without any rules
+    pub fn new() -> Self {
+        Self {
+            sections: HashMap::new(),
+        }
+    }
+
+    /// Adds a rule, that is read from the section with its `section_identifier`
+    pub fn register<R: SudokuRule + 'static>(&mut self) {
+        self.register_section(R::section_identifier(), construct::<R>);
+    }
+
+    /// Adds a constructor for the section with the identifier.
+    /// A section can create multiple rules, like the `lines` section.
+    pub fn register_section(&mut self, identifier: &str, constructor: RuleConstructor) {
+        self.sections
+            .entry(identifier.to_string())
+            .or_default()
+            .push(constructor);
+    }
+
+    /// Returns true if a rule is registered for the section
+    /// ```
+    /// use sudoku::sudokus::RuleRegistry;
+    ///
+    /// assert!(RuleRegistry::default().contains("x-sudoku"));
+    /// assert!(!RuleRegistry::new().contains("x-sudoku"));
+    /// ```
+    pub fn contains(&self, identifier: &str) -> bool {
+        self.sections.contains_key(identifier)
+    }
+
+    /// Returns the constructors of all rules of a section
+    pub(crate) fn constructors(&self, identifier: &str) -> Option<&[RuleConstructor]> {
+        self.sections.get(identifier).map(Vec::as_slice)
+    }
+}
+
+fn construct<R: SudokuRule + 'static>(
+    input: &str,
+    geometry: Geometry,
+) -> Result<Box<dyn SudokuRule>, ParseError> {
+    Ok(R::try_from_str(input, geometry)?)
+}
+
+#[cfg(test)]
+mod rule_registry_test {
+    use super::RuleRegistry;
+
+    #[test]
+    fn lines() {
+        // Whispers, renban and palindromes are all read from the lines section
+        let registry = RuleRegistry::default();
+
+        assert_eq!(
+            registry
+                .constructors("lines")
+                .map(|constructors| constructors.len()),
+            Some(3)
+        );
+        assert!(!registry.contains("whisper"));
+        assert!(registry.constructors("hyper").is_none());
+    }
+}
@@ -0,0 +1,313 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{
+    all_numbers, candidates, invalid_section, parse_clues, reachable_sums, section_content,
+    SudokuRule,
+};
+
+/// Sandwich Sudoku
+///
+/// The clues outside of the field are the sum of the numbers between the smallest and the
+/// biggest number (1 and 9 in a classic Sudoku) of a row or column. Rows are the lines of
+/// the `field` section. Missing clues are written as `-`:
+/// ```text
+/// sandwich:
+/// rows: 13 7 - - 21 13 22 13 0
+/// columns: 0 22 15 15 17 - 24 - 0;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SandwichSudoku {
+    rows: Vec<Option<i32>>,
+    columns: Vec<Option<i32>>,
+
+    /// `fillings[length][sum]` are the numbers that can be in a sandwich of
+    /// `length` cells with the given sum. `None` if there is no such sandwich.
+    fillings: Vec<Vec<Option<u32>>>,
+}
+
+impl SudokuRule for SandwichSudoku {
+    fn str_identifier() -> &'static str {
+        "sandwich"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let size = geometry.size();
+        let mut sandwich = Self {
+            rows: vec![None; size],
+            columns: vec![None; size],
+            fillings: Self::fillings(size),
+        };
+        let biggest_sum = sandwich.fillings[0].len() as i32 - 1;
+
+        for line in section_content(input, Self::str_identifier()).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let Some((name, clues)) = line.split_once(':') else {
+                return Err(invalid_section(
+                    Self::str_identifier(),
+                    format!("Missing line name in {}", line.trim()),
+                ));
+            };
+
+            let clues = parse_clues(clues, size)
+                .filter(|clues| clues.iter().flatten().all(|sum| (0..=biggest_sum).contains(sum)))
+                .ok_or_else(|| {
+                    invalid_section(Self::str_identifier(), format!("Invalid clues in {}", line))
+                })?;
+            match name.trim() {
+                "rows" => sandwich.rows = clues,
+                "columns" => sandwich.columns = clues,
+                _ => {
+                    return Err(invalid_section(
+                        Self::str_identifier(),
+                        format!("Unknown line {}", name),
+                    ))
+                }
+            }
+        }
+
+        Ok(Box::new(sandwich))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for i in 0..field.len() {
+            if let Some(sum) = self.rows[i] {
+                if !Self::line_complys(&Self::row(field, i), sum) {
+                    return false;
+                }
+            }
+
+            if let Some(sum) = self.columns[i] {
+                if !Self::line_complys(&Self::column(field, i), sum) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let mut possible = all_numbers(field);
+
+        // The cell is the row-th cell of the line collum and the other way around
+        if let Some(sum) = self.rows[collum] {
+            possible &= self.possible_in_line(&Self::row(field, collum), sum)[row];
+        }
+
+        if let Some(sum) = self.columns[row] {
+            possible &= self.possible_in_line(&Self::column(field, row), sum)[collum];
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    /// The clues of the rows are left of the field and the clues of the columns above it
+    fn draw(&self, drawing: &mut Drawing) {
+        for (i, (row, column)) in self.rows.iter().zip(&self.columns).enumerate() {
+            if let Some(sum) = row {
+                drawing.text((-0.5, i as f64 + 0.5), &sum.to_string(), 0.45, "#000000");
+            }
+            if let Some(sum) = column {
+                drawing.text((i as f64 + 0.5, -0.5), &sum.to_string(), 0.45, "#000000");
+            }
+        }
+    }
+}
+
+impl SandwichSudoku {
+    /// Returns all sandwiches that can be build out of the numbers between
+    /// the smallest and the biggest number
+    fn fillings(size: usize) -> Vec<Vec<Option<u32>>> {
+        let numbers = 2..size as i32;
+        let biggest_sum = numbers.clone().sum::<i32>().max(0) as usize;
+        let mut fillings = vec![vec![None; biggest_sum + 1]; size.saturating_sub(1).max(1)];
+        fillings[0][0] = Some(0);
+
+        // Every sandwich with a number is a smaller sandwich with all numbers below plus the number
+        for num in numbers {
+            for length in (0..fillings.len() - 1).rev() {
+                for sum in (0..=biggest_sum - num as usize).rev() {
+                    let Some(smaller) = fillings[length][sum] else {
+                        continue;
+                    };
+
+                    let entry = &mut fillings[length + 1][sum + num as usize];
+                    *entry = Some(entry.unwrap_or(0) | smaller | 0b1 << (num - 1));
+                }
+            }
+        }
+
+        fillings
+    }
+
+    /// Returns the cells of a row like they are written in the file
+    fn row(field: &[Vec<u32>], row: usize) -> Vec<u32> {
+        (0..field.len()).map(|i| candidates(field, i, row)).collect()
+    }
+
+    /// Returns the cells of a column like they are written in the file
+    fn column(field: &[Vec<u32>], column: usize) -> Vec<u32> {
+        (0..field.len()).map(|i| candidates(field, column, i)).collect()
+    }
+
+    /// Returns the smallest and the biggest number of a line in the raw format
+    fn bread(line: &[u32]) -> (u32, u32) {
+        (0b1, 0b1 << (line.len() - 1))
+    }
+
+    /// Returns true if the set numbers of a line don't contradict the sum
+    fn line_complys(line: &[u32], sum: i32) -> bool {
+        let (smallest, biggest) = Self::bread(line);
+        let one = line.iter().position(|&n| n == smallest);
+        let nine = line.iter().position(|&n| n == biggest);
+        let (Some(one), Some(nine)) = (one, nine) else {
+            return true;
+        };
+
+        let mut set_sum = 0;
+        let mut unset = 0;
+        for &cell in &line[one.min(nine) + 1..one.max(nine)] {
+            if cell.count_ones() != 1 {
+                unset += 1;
+                continue;
+            }
+            set_sum += cell.trailing_zeros() as i32 + 1;
+        }
+
+        // Every unset cell in the sandwich is at least a 2
+        set_sum + 2 * unset <= sum && (unset != 0 || set_sum == sum)
+    }
+
+    /// Returns the possible numbers of every cell of a line.
+    /// All placements of the smallest and the biggest number are checked,
+    /// if the sandwich between them can have the sum.
+    fn possible_in_line(&self, line: &[u32], sum: i32) -> Vec<u32> {
+        let size = line.len();
+        let (smallest, biggest) = Self::bread(line);
+        let bread = smallest | biggest;
+        let mut possible = vec![0; size];
+
+        for first in 0..size {
+            for last in first + 1..size {
+                let Some(filling) = self.fillings[last - first - 1][sum as usize] else {
+                    continue;
+                };
+
+                let fits = (0..size).all(|i| {
+                    if i == first || i == last {
+                        return true;
+                    }
+                    if i > first && i < last {
+                        return line[i] & filling != 0;
+                    }
+                    line[i] & !bread != 0
+                });
+                if !fits {
+                    continue;
+                }
+
+                let mut sandwich = vec![0; size];
+                for i in first + 1..last {
+                    let others = reachable_sums(
+                        (first + 1..last).filter(|&j| j != i).map(|j| line[j] & filling),
+                    );
+                    for num in 2..size as i32 {
+                        if line[i] & filling & (0b1 << (num - 1)) != 0 && others.contains(sum - num) {
+                            sandwich[i] |= 0b1 << (num - 1);
+                        }
+                    }
+                }
+                if (first + 1..last).any(|i| sandwich[i] == 0) {
+                    continue;
+                }
+
+                for (one, nine) in [(first, last), (last, first)] {
+                    if line[one] & smallest == 0 || line[nine] & biggest == 0 {
+                        continue;
+                    }
+
+                    possible[one] |= smallest;
+                    possible[nine] |= biggest;
+                    for i in 0..size {
+                        if i > first && i < last {
+                            possible[i] |= sandwich[i];
+                        } else if i != first && i != last {
+                            possible[i] |= line[i] & !bread;
+                        }
+                    }
+                }
+            }
+        }
+
+        possible
+    }
+}
+
+#[cfg(test)]
+mod sandwich_sudoku_test {
+    use super::SandwichSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        // Numbers from: https://de.wikipedia.org/wiki/Sudoku#/media/Datei:Sudoku_variant.png
+        let field = [
+            [1, 2, 3, 7, 8, 9, 4, 5, 6],
+            [4, 5, 6, 1, 2, 3, 7, 8, 9],
+            [7, 8, 9, 4, 5, 6, 1, 2, 3],
+            [2, 3, 1, 8, 9, 7, 5, 6, 4],
+            [5, 6, 4, 2, 3, 1, 8, 9, 7],
+            [8, 9, 7, 5, 6, 4, 2, 3, 1],
+            [3, 1, 2, 9, 7, 8, 6, 4, 5],
+            [6, 4, 5, 3, 1, 2, 9, 7, 8],
+            [9, 7, 8, 6, 4, 5, 3, 1, 2],
+        ]
+        .map(|r| r.map(|n: u32| 0b1 << (n - 1)).to_vec())
+        .to_vec();
+
+        let good = SandwichSudoku::from_str(
+            "sandwich:\nrows: 35 - - - - - - - -\ncolumns: 20 - - - - - - - -",
+            Geometry::CLASSIC,
+        );
+        let bad = SandwichSudoku::from_str(
+            "sandwich:\nrows: 34 - - - - - - - -\ncolumns: - - - - - - - - -",
+            Geometry::CLASSIC,
+        );
+
+        assert!(good.complys(&field));
+        assert!(!bad.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let field = vec![vec![0; 9]; 9];
+
+        // A sandwich of 0 means, that 1 and 9 are next to each other
+        let zero = SandwichSudoku::from_str(
+            "sandwich:\nrows: 0 - - - - - - - -\ncolumns: - - - - - - - - -",
+            Geometry::CLASSIC,
+        );
+        for i in 0..9 {
+            assert_eq!(zero.get_not_possible_numbers_raw(&field, i, 0), 0);
+        }
+
+        // 35 is only possible with 1 and 9 at the ends of the row
+        let full = SandwichSudoku::from_str(
+            "sandwich:\nrows: 35 - - - - - - - -\ncolumns: - - - - - - - - -",
+            Geometry::CLASSIC,
+        );
+        assert_eq!(full.get_not_possible_numbers_raw(&field, 0, 0), 0b011_111_110);
+        assert_eq!(full.get_not_possible_numbers_raw(&field, 4, 0), 0b100_000_001);
+        assert_eq!(full.get_not_possible_numbers_raw(&field, 4, 1), 0);
+    }
+}
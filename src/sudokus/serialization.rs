@@ -0,0 +1,245 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::fast_sudoku::FastSudoku;
+use super::geometry::Geometry;
+use super::rule_registry::RuleRegistry;
+use super::sudoku::{Moves, Sudoku};
+
+/// The JSON of a Sudoku:
+/// ```text
+/// {
+///   "size": 9,
+///   "box_width": 3,
+///   "box_height": 3,
+///   "field": [[[5], [], [1, 2], ...], ...],
+///   "givens": [[5, 0, 0, ...], ...],
+///   "rules": ["x-sudoku", "killer:\n15 r1c1 r1c2"],
+///   "moves": { "missing_moves": 0, "alone_moves": 0, ... },
+///   "solved": false,
+///   "correct": false
+/// }
+/// ```
+///
+/// `field` has the lines of the `field` section with the numbers of every cell.
+/// A cell with a single number is set and an empty cell hasn't been looked at.
+/// `givens` has the lines with the numbers of the puzzle and `0` for the other cells.
+/// `rules` are the sections of the rules like they are written in a file.
+/// `solved` and `correct` are only written and ignored when reading.
+#[derive(Serialize, Deserialize)]
+struct SudokuData {
+    size: usize,
+    box_width: usize,
+    box_height: usize,
+    field: Vec<Vec<Vec<i32>>>,
+
+    #[serde(default)]
+    givens: Vec<Vec<i32>>,
+
+    #[serde(default)]
+    rules: Vec<String>,
+
+    #[serde(default = "Moves::empty")]
+    moves: Moves,
+
+    #[serde(default, skip_deserializing)]
+    solved: bool,
+
+    #[serde(default, skip_deserializing)]
+    correct: bool,
+}
+
+/// The JSON of a FastSudoku. It is the `field` of a Sudoku, that is always 9x9.
+#[derive(Serialize, Deserialize)]
+struct FastSudokuData {
+    field: Vec<Vec<Vec<i32>>>,
+}
+
+impl Serialize for Sudoku {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let geometry = self.geometry();
+        let size = geometry.size();
+
+        SudokuData {
+            size,
+            box_width: geometry.box_width(),
+            box_height: geometry.box_height(),
+            field: (0..size)
+                .map(|collum| (0..size).map(|row| self.get_numbers(row, collum)).collect())
+                .collect(),
+            givens: (0..size)
+                .map(|collum| {
+                    (0..size)
+                        .map(|row| match self.is_given(row, collum) {
+                            true => self.get_number(row, collum),
+                            false => 0,
+                        })
+                        .collect()
+                })
+                .collect(),
+            rules: self.sections.clone(),
+            moves: self.moves.clone(),
+            solved: self.is_solved(),
+            correct: self.is_correct(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Sudoku {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SudokuData::deserialize(deserializer)?;
+        let geometry = Geometry::new(data.size, data.box_width, data.box_height)
+            .ok_or_else(|| D::Error::custom("The boxes don't fill the field"))?;
+
+        let mut sudoku = Sudoku::with_geometry(geometry);
+        sudoku.field = raw_field(&data.field, geometry.size()).map_err(D::Error::custom)?;
+        for (collum, line) in data.givens.iter().enumerate().take(geometry.size()) {
+            for (row, &number) in line.iter().enumerate().take(geometry.size()) {
+                if number != 0 && sudoku.get_number(row, collum) != number {
+                    return Err(D::Error::custom(format!(
+                        "The given {} isn't the number of its cell",
+                        number
+                    )));
+                }
+                sudoku.givens[row][collum] = number != 0;
+            }
+        }
+
+        let registry = RuleRegistry::default();
+        for section in &data.rules {
+            sudoku
+                .add_section(section, &registry)
+                .map_err(D::Error::custom)?;
+        }
+        sudoku.moves = data.moves;
+
+        Ok(sudoku)
+    }
+}
+
+impl Serialize for FastSudoku {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let numbers = |row: usize, collum: usize| {
+            (1..=9)
+                .filter(|number| self.field[row][collum] & (0b1 << (number - 1)) != 0)
+                .collect()
+        };
+
+        FastSudokuData {
+            field: (0..9)
+                .map(|collum| (0..9).map(|row| numbers(row, collum)).collect())
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FastSudoku {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = FastSudokuData::deserialize(deserializer)?;
+        let field = raw_field(&data.field, 9).map_err(D::Error::custom)?;
+
+        let mut sudoku = FastSudoku::empty();
+        for (row, numbers) in field.into_iter().enumerate() {
+            for (collum, number) in numbers.into_iter().enumerate() {
+                sudoku.field[row][collum] = number as i32;
+            }
+        }
+        Ok(sudoku)
+    }
+}
+
+/// Returns the field in the raw format out of the lines with the numbers of every cell
+fn raw_field(lines: &[Vec<Vec<i32>>], size: usize) -> Result<Vec<Vec<u32>>, String> {
+    if lines.len() != size || lines.iter().any(|line| line.len() != size) {
+        return Err(format!(
+            "The field needs {} lines with {} cells",
+            size, size
+        ));
+    }
+
+    let mut field = vec![vec![0; size]; size];
+    for (collum, line) in lines.iter().enumerate() {
+        for (row, numbers) in line.iter().enumerate() {
+            for &number in numbers {
+                if !(1..=size as i32).contains(&number) {
+                    return Err(format!(
+                        "{} is not a number of a Sudoku of size {}",
+                        number, size
+                    ));
+                }
+                field[row][collum] |= 0b1 << (number - 1);
+            }
+        }
+    }
+
+    Ok(field)
+}
+
+#[cfg(test)]
+mod serialization_test {
+    use crate::sudokus::{FastSudoku, Sudoku};
+
+    #[test]
+    fn sudoku() {
+        let mut sudoku =
+            Sudoku::from_file("test-sudokus/text/constructed-26-10-18-thermo-hard-unsolved");
+        let json = serde_json::to_value(&sudoku).unwrap();
+        assert_eq!(json["size"], 9);
+        assert_eq!(json["field"][0][4], serde_json::json!([3]));
+        assert_eq!(json["givens"][0][4], 3);
+        assert!(json["rules"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("thermo:\nr1c2 r2c1 r3c1"));
+        assert_eq!(json["solved"], false);
+
+        let mut read: Sudoku = serde_json::from_value(json).unwrap();
+        assert_eq!(read, sudoku);
+
+        // The rules are read again
+        assert!(read.solve());
+        assert!(sudoku.solve());
+        assert_eq!(read, sudoku);
+
+        let json = serde_json::to_value(&sudoku).unwrap();
+        assert_eq!(json["solved"], true);
+        assert_eq!(json["correct"], true);
+        assert_eq!(json["givens"][0][0], 0);
+
+        let read: Sudoku = serde_json::from_value(json).unwrap();
+        assert!(read.is_given(4, 0));
+        assert!(!read.is_given(0, 0));
+    }
+
+    #[test]
+    fn fast_sudoku() {
+        let sudoku = FastSudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved");
+        let json = serde_json::to_string(&sudoku).unwrap();
+        assert!(json.starts_with(r#"{"field":[[[1],[],[],[],[9],"#));
+
+        let read: FastSudoku = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, sudoku);
+    }
+
+    #[test]
+    fn invalid() {
+        let json = r#"{"size": 4, "box_width": 3, "box_height": 2, "field": []}"#;
+        assert!(serde_json::from_str::<Sudoku>(json).is_err());
+
+        let json = r#"{"field": [[[10]]]}"#;
+        assert!(serde_json::from_str::<FastSudoku>(json).is_err());
+
+        let json = r#"{"size": 1, "box_width": 1, "box_height": 1, "field": [[[]]], "givens": [[1]]}"#;
+        assert!(serde_json::from_str::<Sudoku>(json).is_err());
+
+        let json =
+            r#"{"size": 1, "box_width": 1, "box_height": 1, "field": [[[]]], "rules": ["hyper"]}"#;
+        assert!(serde_json::from_str::<Sudoku>(json).is_err());
+
+        // A malformed section of a known rule is an error and not a panic
+        let json = r#"{"size": 1, "box_width": 1, "box_height": 1, "field": [[[]]], "rules": ["killer:\nfoo r1c1"]}"#;
+        assert!(serde_json::from_str::<Sudoku>(json).is_err());
+    }
+}
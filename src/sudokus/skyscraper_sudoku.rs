@@ -0,0 +1,374 @@
+use std::cell::RefCell;
+
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{
+    all_numbers, candidates, invalid_section, parse_clues, section_content, SudokuRule,
+};
+
+/// The candidates of a line with the clues from its start and end
+type LineKey = (Vec<u32>, Option<i32>, Option<i32>);
+
+/// The possible numbers of every cell of a line for its key
+type Cached = Option<(LineKey, Vec<u32>)>;
+
+/// A row or column with the clues from both of its ends
+#[derive(Debug, Clone)]
+struct Line {
+    cells: Vec<(usize, usize)>,
+    start: Option<i32>,
+    end: Option<i32>,
+}
+
+/// Skyscraper Sudoku
+///
+/// Every number is the height of a building. The clues outside of the field
+/// are the number of buildings that can be seen from that side of a row or column.
+/// Higher buildings hide the lower ones behind them. Rows are the lines of the
+/// `field` section. Missing clues are written as `-`:
+/// ```text
+/// skyscraper:
+/// top: 3 - 3 - 6 - 2 - 5
+/// bottom: - 3 3 4 1 - 3 - 2
+/// left: 3 3 2 - 2 - 1 5 -
+/// right: - - 3 3 2 1 3 2 2;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkyscraperSudoku {
+    lines: Vec<Line>,
+
+    /// The possible numbers of a line only depend on its candidates and clues.
+    /// They are needed for every cell of the line, so the last ones of every line are cached.
+    cache: RefCell<Vec<Cached>>,
+}
+
+impl SudokuRule for SkyscraperSudoku {
+    fn str_identifier() -> &'static str {
+        "skyscraper"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let size = geometry.size();
+        let mut top = vec![None; size];
+        let mut bottom = vec![None; size];
+        let mut left = vec![None; size];
+        let mut right = vec![None; size];
+
+        for line in section_content(input, Self::str_identifier()).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let Some((name, clues)) = line.split_once(':') else {
+                return Err(invalid_section(
+                    Self::str_identifier(),
+                    format!("Missing line name in {}", line.trim()),
+                ));
+            };
+
+            let clues = parse_clues(clues, size)
+                .filter(|clues| clues.iter().flatten().all(|&seen| (1..=size as i32).contains(&seen)))
+                .ok_or_else(|| {
+                    invalid_section(Self::str_identifier(), format!("Invalid clues in {}", line))
+                })?;
+            match name.trim() {
+                "top" => top = clues,
+                "bottom" => bottom = clues,
+                "left" => left = clues,
+                "right" => right = clues,
+                _ => {
+                    return Err(invalid_section(
+                        Self::str_identifier(),
+                        format!("Unknown line {}", name),
+                    ))
+                }
+            }
+        }
+
+        let mut lines = vec![];
+        for i in 0..size {
+            // The row i of the file are the cells field[0..size][i]
+            lines.push(Line {
+                cells: (0..size).map(|j| (j, i)).collect(),
+                start: left[i],
+                end: right[i],
+            });
+            lines.push(Line {
+                cells: (0..size).map(|j| (i, j)).collect(),
+                start: top[i],
+                end: bottom[i],
+            });
+        }
+        lines.retain(|line| line.start.is_some() || line.end.is_some());
+
+        Ok(Box::new(Self {
+            cache: RefCell::new(vec![None; lines.len()]),
+            lines,
+        }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        for line in &self.lines {
+            let numbers: Vec<_> = line.cells.iter().map(|&(row, collum)| field[row][collum]).collect();
+
+            if !Self::view_complys(numbers.iter(), line.start)
+                || !Self::view_complys(numbers.iter().rev(), line.end)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        let mut possible = all_numbers(field);
+
+        for (number, line) in self.lines.iter().enumerate() {
+            let Some(index) = line.cells.iter().position(|&cell| cell == (row, collum)) else {
+                continue;
+            };
+
+            let candidates: Vec<_> = line.cells.iter().map(|&(r, c)| candidates(field, r, c)).collect();
+            let key = (candidates, line.start, line.end);
+
+            let cached = match &self.cache.borrow()[number] {
+                Some((cached_key, possible)) if *cached_key == key => Some(possible[index]),
+                _ => None,
+            };
+            let possible_in_cell = match cached {
+                Some(possible_in_cell) => possible_in_cell,
+                None => {
+                    let possible_in_line =
+                        Search::possible_in_line(key.0.clone(), line.start, line.end);
+                    let possible_in_cell = possible_in_line[index];
+                    self.cache.borrow_mut()[number] = Some((key, possible_in_line));
+                    possible_in_cell
+                }
+            };
+
+            possible &= possible_in_cell;
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    /// The clues are written outside of the field, next to the first and the last cell of their line
+    fn draw(&self, drawing: &mut Drawing) {
+        for line in self.lines.iter().filter(|line| line.cells.len() > 1) {
+            let ends = [
+                (line.start, line.cells[0], line.cells[1]),
+                (line.end, line.cells[line.cells.len() - 1], line.cells[line.cells.len() - 2]),
+            ];
+            for (clue, end, next) in ends {
+                let Some(clue) = clue else {
+                    continue;
+                };
+                let (x, y) = Drawing::center(end);
+                let (next_x, next_y) = Drawing::center(next);
+                drawing.text((2.0 * x - next_x, 2.0 * y - next_y), &clue.to_string(), 0.45, "#000000");
+            }
+        }
+    }
+}
+
+impl SkyscraperSudoku {
+    /// Returns true if the set buildings at the start of a line don't see too many buildings
+    fn view_complys<'a>(numbers: impl Iterator<Item = &'a u32>, seen: Option<i32>) -> bool {
+        let Some(seen) = seen else {
+            return true;
+        };
+
+        let mut highest = 0;
+        let mut visible = 0;
+        for &number in numbers {
+            if number.count_ones() != 1 {
+                return visible <= seen;
+            }
+
+            if number > highest {
+                highest = number;
+                visible += 1;
+            }
+        }
+
+        visible == seen
+    }
+}
+
+/// Depth first search over all orders of the numbers of a line, that fit the candidates
+struct Search {
+    candidates: Vec<u32>,
+    start: Option<i32>,
+    end: Option<i32>,
+
+    numbers: Vec<u32>,
+    possible: Vec<u32>,
+}
+
+impl Search {
+    /// Returns the possible numbers of all cells of a line
+    fn possible_in_line(candidates: Vec<u32>, start: Option<i32>, end: Option<i32>) -> Vec<u32> {
+        let size = candidates.len();
+        let mut search = Search {
+            candidates,
+            start,
+            end,
+            numbers: vec![0; size],
+            possible: vec![0; size],
+        };
+        search.search(0, 0, 0, 0);
+        search.possible
+    }
+
+    /// Returns true if all candidates are already possible, so the search can stop
+    fn is_done(&self) -> bool {
+        self.possible == self.candidates
+    }
+
+    fn search(&mut self, index: usize, used: u32, highest: u32, visible: i32) {
+        let size = self.candidates.len();
+        if index == size {
+            if self.start.is_some_and(|start| start != visible) {
+                return;
+            }
+
+            if let Some(end) = self.end {
+                let mut highest_from_end = 0;
+                let mut visible_from_end = 0;
+                for &number in self.numbers.iter().rev() {
+                    if number > highest_from_end {
+                        highest_from_end = number;
+                        visible_from_end += 1;
+                    }
+                }
+                if visible_from_end != end {
+                    return;
+                }
+            }
+
+            for (possible, number) in self.possible.iter_mut().zip(&self.numbers) {
+                *possible |= number;
+            }
+            return;
+        }
+
+        if let Some(start) = self.start {
+            // The biggest number will be seen for sure, every left number higher than the highest might be seen
+            let not_higher = if highest == 0 { 0 } else { (highest << 1) - 1 };
+            let all = (0b1 << size) - 1;
+            let higher_left = (all & !used & !not_higher).count_ones() as i32;
+            let at_least = if highest == 0b1 << (size - 1) { 0 } else { 1 };
+            if visible + at_least > start || visible + higher_left < start {
+                return;
+            }
+        }
+
+        for num in 0..size {
+            let number = 0b1 << num;
+            if used & number != 0 || self.candidates[index] & number == 0 {
+                continue;
+            }
+
+            self.numbers[index] = number;
+            let seen = (number > highest) as i32;
+            self.search(
+                index + 1,
+                used | number,
+                highest.max(number),
+                visible + seen,
+            );
+
+            if self.is_done() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod skyscraper_sudoku_test {
+    use super::{Search, SkyscraperSudoku};
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        // The first row is 5 4 6 9 2 1 7 8 3
+        for (i, num) in [5, 4, 6, 9, 2, 1, 7, 8, 3].into_iter().enumerate() {
+            field[i][0] = 0b1 << (num - 1);
+        }
+
+        let good = SkyscraperSudoku::from_str(
+            "skyscraper:\nleft: 3 - - - - - - - -\nright: 3 - - - - - - - -",
+            Geometry::CLASSIC,
+        );
+        let bad = SkyscraperSudoku::from_str("skyscraper:\nleft: 2 - - - - - - - -", Geometry::CLASSIC);
+
+        assert!(good.complys(&field));
+        assert!(!bad.complys(&field));
+
+        // With the 9 missing, only too many buildings are wrong
+        field[3][0] = 0;
+        assert!(good.complys(&field));
+        assert!(bad.complys(&field));
+        let wrong = SkyscraperSudoku::from_str("skyscraper:\nleft: 1 - - - - - - - -", Geometry::CLASSIC);
+        assert!(!wrong.complys(&field));
+    }
+
+    #[test]
+    fn search() {
+        let all = vec![0b111_111_111; 9];
+
+        // Only 1 building can be seen, if the 9 is in the front
+        let possible = Search::possible_in_line(all.clone(), Some(1), None);
+        assert_eq!(possible[0], 0b100_000_000);
+        assert_eq!(possible[1], 0b011_111_111);
+
+        // 9 buildings can only be seen, if they are sorted
+        let possible = Search::possible_in_line(all.clone(), Some(9), None);
+        for (i, possible) in possible.into_iter().enumerate() {
+            assert_eq!(possible, 0b1 << i);
+        }
+
+        // Seeing only 1 from the end doesn't fit with a 8 at the end
+        let mut candidates = all;
+        candidates[8] = 0b10_000_000;
+        assert_eq!(
+            Search::possible_in_line(candidates, Some(3), Some(1)),
+            vec![0; 9]
+        );
+    }
+
+    #[test]
+    fn not_possible() {
+        let field = vec![vec![0; 9]; 9];
+        let skyscraper = SkyscraperSudoku::from_str("skyscraper:\ntop: 1 - - - - - - - -", Geometry::CLASSIC);
+
+        assert_eq!(
+            skyscraper.get_not_possible_numbers_raw(&field, 0, 0),
+            0b011_111_111
+        );
+        assert_eq!(
+            skyscraper.get_not_possible_numbers_raw(&field, 0, 1),
+            0b100_000_000
+        );
+        assert_eq!(skyscraper.get_not_possible_numbers_raw(&field, 1, 0), 0);
+
+        // Only the last candidates of every line are cached
+        let mut field = field;
+        field[0][1] = 0b000_000_001;
+        assert_eq!(
+            skyscraper.get_not_possible_numbers_raw(&field, 0, 2),
+            0b100_000_001
+        );
+        assert_eq!(skyscraper.cache.borrow().len(), 1);
+    }
+}
@@ -3,18 +3,32 @@ use std::fs;
 use std::fmt::Debug;
 use crate::sudokus::SudokuRule;
 
-use super::even_odd_sudoku::EvenOddSudoku;
-use super::x_sudoku::XSudoku;
+use super::drawing::Drawing;
+use super::f_puzzles;
+use super::file_format::FileFormat;
+use super::geometry::{Geometry, SIZE_IDENTIFIER};
+use super::line_format;
+use super::parse_error::ParseError;
+use super::pencil_marks::{self, CANDIDATES_IDENTIFIER};
+use super::picture;
+use super::pretty::Pretty;
+use super::rule_registry::RuleRegistry;
+use super::sudoku_rule::{invalid_section, section_content, section_identifier};
+
+/// Identifier of the section with the numbers of the field
+const FIELD_IDENTIFIER: &str = "field";
+
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Moves {
-    missing_moves: u32,
-    alone_moves: u32,
+    pub(crate) missing_moves: u32,
+    pub(crate) alone_moves: u32,
     player_moves: u32,
-    bruteforce_moves: u32,
-    bruteforce_failed_tries: u32,
-    failed_missing_moves: u32,
-    failed_alone_moves: u32,
+    pub(crate) bruteforce_moves: u32,
+    pub(crate) bruteforce_failed_tries: u32,
+    pub(crate) failed_missing_moves: u32,
+    pub(crate) failed_alone_moves: u32,
 }
 
 impl Moves {
@@ -33,10 +47,23 @@ impl Moves {
 
 /// Sudoku. It contains the data of the field and counts the moves
 pub struct Sudoku {
-    pub field: [[i32; 9]; 9],
-    moves: Moves,
+    pub field: Vec<Vec<u32>>,
+    geometry: Geometry,
+    pub(crate) moves: Moves,
+
+    /// Cells with a number of the puzzle, indexed like `field`
+    pub(crate) givens: Vec<Vec<bool>>,
 
     rules: Vec<Box<dyn SudokuRule>>,
+
+    /// The sections of the rules, to write them again
+    pub(crate) sections: Vec<String>,
+
+    /// Extra groups of the rules that can't contain a number twice
+    houses: Vec<Vec<(usize, usize)>>,
+
+    /// Extra cells of the rules and houses that see a cell, indexed like `field`
+    peers: Vec<Vec<Vec<(usize, usize)>>>,
 }
 
 impl PartialEq for Sudoku {
@@ -45,43 +72,90 @@ impl PartialEq for Sudoku {
     }
 }
 
+impl fmt::Display for Moves {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Missing moves: {} ({} failed)", self.missing_moves, self.failed_missing_moves)?;
+        writeln!(f, "Alone moves: {} ({} failed)", self.alone_moves, self.failed_alone_moves)?;
+        writeln!(f, "Bruteforce moves: {} ({} failed tries)", self.bruteforce_moves, self.bruteforce_failed_tries)?;
+        write!(f, "Player moves: {}", self.player_moves)
+    }
+}
+
+/// Writes the Sudoku in the format of a file, that can be read again with `Sudoku::parse`.
+/// It has the given numbers as `field`, the `size` if it isn't a classic Sudoku, the `candidates`
+/// if other cells are solved or have some and the sections of the rules.
+/// `{:#}` draws the Sudoku for the terminal, see `Sudoku::pretty`.
 impl fmt::Display for Sudoku {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for collum in 0..9 {
-            for row in 0..9 {
-                write!(f, "{} ", self.get_number(row, collum))?;
-                if row % 3 == 2 {
+        if f.alternate() {
+            return write!(f, "{}", self.pretty());
+        }
+
+        let size = self.geometry.size();
+        if self.geometry != Geometry::CLASSIC {
+            write!(f, "{}: {}", SIZE_IDENTIFIER, size)?;
+            if Geometry::with_size(size) != Some(self.geometry) {
+                write!(f, "\nbox: {}x{}", self.geometry.box_width(), self.geometry.box_height())?;
+            }
+            writeln!(f, ";")?;
+            writeln!(f)?;
+        }
+
+        writeln!(f, "{}:", FIELD_IDENTIFIER)?;
+        for collum in 0..size {
+            if collum != 0 && collum % self.geometry.box_height() == 0 {
+                writeln!(f)?;
+            }
+            for row in 0..size {
+                if row != 0 && row % self.geometry.box_width() == 0 {
+                    write!(f, "  ")?;
+                } else if row != 0 {
                     write!(f, " ")?;
                 }
+                let number = match self.is_given(row, collum) {
+                    true => self.get_number(row, collum),
+                    false => 0,
+                };
+                write!(f, "{}", Geometry::number_to_char(number))?;
             }
-            write!(f, "\n")?;
-            if collum % 3 == 2 {
-                write!(f, "\n")?;
+            match collum == size - 1 {
+                true => writeln!(f, ";")?,
+                false => writeln!(f)?,
             }
         }
 
-        write!(f, "Moves: {:#?} ", self.moves)?;
+        // Solved numbers are candidates, so they aren't read as given numbers
+        let has_candidates = (0..size * size)
+            .any(|count| !self.is_given(count % size, count / size) && self.field[count % size][count / size] != 0);
+        if has_candidates {
+            writeln!(f)?;
+            write!(f, "{}:\n{};", CANDIDATES_IDENTIFIER, self.to_pencil_marks())?;
+            writeln!(f)?;
+        }
+
+        for section in &self.sections {
+            writeln!(f)?;
+            writeln!(f, "{};", section)?;
+        }
         Ok(())
     }
 }
 
+/// Writes the size, the candidates of every cell, the rules and the moves
 impl fmt::Debug for Sudoku {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for collum in 0..9 {
-            for row in 0..9 {
-                write!(f, "{} ", self.get_number(row, collum))?;
-                if row % 3 == 2 {
-                    write!(f, " ")?;
-                }
-            }
-            write!(f, "\n")?;
-            if collum % 3 == 2 {
-                write!(f, "\n")?;
-            }
-        }
-
-        write!(f, "Moves: {:#?} ", self.moves)?;
-        Ok(())
+        let rules: Vec<&str> = self.sections.iter().map(|section| section_identifier(section)).collect();
+
+        writeln!(
+            f,
+            "Sudoku of size {} with boxes of {}x{}",
+            self.geometry.size(),
+            self.geometry.box_width(),
+            self.geometry.box_height()
+        )?;
+        write!(f, "{}", self.to_pencil_marks())?;
+        writeln!(f, "Rules: [{}]", rules.join(", "))?;
+        write!(f, "Moves: {:?}", self.moves)
     }
 }
 
@@ -91,7 +165,7 @@ impl Sudoku {
     /// {website}-{date of retival}-{level}-{solved/unsolved}
     ///
     /// For data structure see Sudoku::from_file
-    pub const UNSOLVED_SUDOKU_FILES: [&'static str; 12] = [
+    pub const UNSOLVED_SUDOKU_FILES: [&'static str; 26] = [
         "test-sudokus/text/tagesspiegel-23-11-11-easy-unsolved",
         "test-sudokus/text/welt-23-11-11-medium-unsolved",
         "test-sudokus/text/welt-23-11-11-hard-unsolved",
@@ -103,23 +177,87 @@ impl Sudoku {
         "test-sudokus/text/sudoku-com-24-01-06-x-sudoku-easy-unsolved",
         "test-sudokus/text/sudoku-com-24-01-06-x-sudoku-master-unsolved",
         "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-easy-unsolved",
-        "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-unsolved"
+        "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-arrow-medium-unsolved",
+        "test-sudokus/text/constructed-26-10-18-sandwich-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-kropki-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-xv-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-anti-knight-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-anti-king-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-non-consecutive-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-lines-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-little-killer-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-skyscraper-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-disjoint-groups-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-hyper-medium-unsolved",
+        "test-sudokus/text/constructed-26-10-18-killer-hard-unsolved",
+        "test-sudokus/text/constructed-26-10-18-thermo-hard-unsolved"
     ];
 
-    pub const SUDOKUS_WITH_SOLUTION: [[&'static str; 2]; 8] = [
+    pub const SUDOKUS_WITH_SOLUTION: [[&'static str; 2]; 26] = [
         // Normal
         ["test-sudokus/text/sudoku-com-12-11-12-master-unsolved", "test-sudokus/text/sudoku-com-12-11-12-master-solved"],
         ["test-sudokus/text/welt-23-11-11-medium-unsolved", "test-sudokus/text/welt-23-11-11-medium-solved"],
         ["test-sudokus/text/welt-23-11-11-easy-unsolved", "test-sudokus/text/welt-23-11-11-easy-solved"],
         ["test-sudokus/text/tagesspiegel-23-11-11-easy-unsolved", "test-sudokus/text/tagesspiegel-23-11-11-easy-solved"],
 
+        // Pencil marks
+        ["test-sudokus/text/sudoku-com-12-11-12-master-candidates", "test-sudokus/text/sudoku-com-12-11-12-master-solved"],
+
         // X-Sudoku
         ["test-sudokus/text/sudoku-com-24-01-06-x-sudoku-easy-unsolved", "test-sudokus/text/sudoku-com-24-01-06-x-sudoku-easy-solved"],
         ["test-sudokus/text/sudoku-com-24-01-06-x-sudoku-master-unsolved", "test-sudokus/text/sudoku-com-24-01-06-x-sudoku-master-solved"],
 
         // Even-Odd-Sudoku
         ["test-sudokus/text/sudokuzok-de-24-01-07-evenodd-easy-unsolved", "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-easy-solved"],
-        ["test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-unsolved", "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-solved"]
+        ["test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-unsolved", "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-extra-hard-solved"],
+
+        // Arrow-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-arrow-medium-unsolved", "test-sudokus/text/constructed-26-10-18-arrow-medium-solved"],
+
+        // Sandwich-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-sandwich-hard-unsolved", "test-sudokus/text/constructed-26-10-18-sandwich-hard-solved"],
+
+        // Kropki-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-kropki-hard-unsolved", "test-sudokus/text/constructed-26-10-18-kropki-hard-solved"],
+
+        // XV-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-xv-hard-unsolved", "test-sudokus/text/constructed-26-10-18-xv-hard-solved"],
+
+        // Anti-Knight-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-anti-knight-hard-unsolved", "test-sudokus/text/constructed-26-10-18-anti-knight-hard-solved"],
+
+        // Anti-King-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-anti-king-hard-unsolved", "test-sudokus/text/constructed-26-10-18-anti-king-hard-solved"],
+
+        // Non-Consecutive-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-non-consecutive-hard-unsolved", "test-sudokus/text/constructed-26-10-18-non-consecutive-hard-solved"],
+
+        // German Whispers, Renban and Palindrome lines
+        ["test-sudokus/text/constructed-26-10-18-lines-hard-unsolved", "test-sudokus/text/constructed-26-10-18-lines-hard-solved"],
+
+        // Little-Killer-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-little-killer-hard-unsolved", "test-sudokus/text/constructed-26-10-18-little-killer-hard-solved"],
+
+        // Skyscraper-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-skyscraper-hard-unsolved", "test-sudokus/text/constructed-26-10-18-skyscraper-hard-solved"],
+
+        // Disjoint-Groups-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-disjoint-groups-hard-unsolved", "test-sudokus/text/constructed-26-10-18-disjoint-groups-hard-solved"],
+
+        // Hyper-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-hyper-medium-unsolved", "test-sudokus/text/constructed-26-10-18-hyper-medium-solved"],
+
+        // Killer-Sudoku without given numbers
+        ["test-sudokus/text/constructed-26-10-18-killer-hard-unsolved", "test-sudokus/text/constructed-26-10-18-killer-hard-solved"],
+
+        // Thermo-Sudoku
+        ["test-sudokus/text/constructed-26-10-18-thermo-hard-unsolved", "test-sudokus/text/constructed-26-10-18-thermo-hard-solved"],
+
+        // Other sizes
+        ["test-sudokus/text/constructed-26-10-18-4x4-easy-unsolved", "test-sudokus/text/constructed-26-10-18-4x4-easy-solved"],
+        ["test-sudokus/text/constructed-26-10-18-6x6-medium-unsolved", "test-sudokus/text/constructed-26-10-18-6x6-medium-solved"],
+        ["test-sudokus/text/constructed-26-10-18-16x16-hard-unsolved", "test-sudokus/text/constructed-26-10-18-16x16-hard-solved"]
     ];
 
     /// Returns an empty Sudoku
@@ -129,10 +267,26 @@ impl Sudoku {
     /// let sudoku = Sudoku::empty();
     /// ```
     pub fn empty() -> Self {
+        Sudoku::with_geometry(Geometry::CLASSIC)
+    }
+
+    /// Returns an empty Sudoku of any size
+    /// ```
+    /// use sudoku::sudokus::{Geometry, Sudoku};
+    ///
+    /// let sudoku = Sudoku::with_geometry(Geometry::with_size(16).unwrap());
+    /// assert_eq!(sudoku.count_unset(), 256);
+    /// ```
+    pub fn with_geometry(geometry: Geometry) -> Self {
         Self {
-            field: [[0; 9]; 9],
+            field: vec![vec![0; geometry.size()]; geometry.size()],
+            geometry,
             moves: Moves::empty(),
+            givens: vec![vec![false; geometry.size()]; geometry.size()],
             rules: vec![],
+            sections: vec![],
+            houses: vec![],
+            peers: vec![vec![vec![]; geometry.size()]; geometry.size()],
         }
     }
 
@@ -166,42 +320,388 @@ impl Sudoku {
     /// let sudoku = Sudoku::from_file("test-sudokus/text/sudoku-com-12-11-12-master-solved");
     /// ```
     pub fn from_file(file_path: &str) -> Self {
-        let mut sudoku = Sudoku::empty();
+        Sudoku::parse(&fs::read_to_string(file_path).expect("todo"))
+    }
 
-        for data in fs::read_to_string(file_path).expect("todo").split(";") {
-            
-            if data.len() == 0 {
-                continue;
-            }
-            if data.contains("field") {
-                let mut count = 0;
-                for c in data.chars() {
-                    if count == 81 {
-                        break;
+    /// Returns an Sudoku from File with the rules of the registry
+    /// ```
+    /// use sudoku::sudokus::{RuleRegistry, Sudoku};
+    ///
+    /// let path = "test-sudokus/text/sudoku-com-24-01-06-x-sudoku-easy-unsolved";
+    /// assert!(Sudoku::from_file_with_registry(path, &RuleRegistry::default()).is_ok());
+    /// assert!(Sudoku::from_file_with_registry(path, &RuleRegistry::new()).is_err());
+    /// ```
+    pub fn from_file_with_registry(file_path: &str, registry: &RuleRegistry) -> Result<Self, ParseError> {
+        let content = fs::read_to_string(file_path).map_err(|error| ParseError::Io(error.to_string()))?;
+        Sudoku::parse_with_registry(&content, registry)
+    }
+
+    /// Returns an Sudoku from the content of a file
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let sudoku = Sudoku::parse("field: 0 3 0 4 0 0 1 8 0; x-sudoku;");
+    /// assert_eq!(sudoku.get_number(1, 0), 3);
+    ///
+    /// // Numbers above 9 are letters
+    /// let sudoku = Sudoku::parse("size: 16; field: 0 G A;");
+    /// assert_eq!(sudoku.get_number(1, 0), 16);
+    /// ```
+    pub fn parse(content: &str) -> Self {
+        // ToDo: Return Result and not just panic if their is an error while parsing
+        Sudoku::parse_with_registry(content, &RuleRegistry::default()).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Returns an Sudoku from the content of a file with the rules of the registry.
+    ///
+    /// Every section has to be the `field`, the `size` or a section of a registered rule.
+    /// ```
+    /// use sudoku::sudokus::{ParseError, RuleRegistry, Sudoku};
+    ///
+    /// let result = Sudoku::parse_with_registry("field: 0 3; hyper;", &RuleRegistry::default());
+    /// assert_eq!(result.err(), Some(ParseError::UnknownSection("hyper".to_string())));
+    /// ```
+    pub fn parse_with_registry(content: &str, registry: &RuleRegistry) -> Result<Self, ParseError> {
+        let geometry = match content.split(";").find(|data| section_identifier(data) == SIZE_IDENTIFIER) {
+            Some(data) => Geometry::from_str(data).ok_or_else(|| {
+                invalid_section(SIZE_IDENTIFIER, format!("No supported size in {}", data.trim()))
+            })?,
+            None => Geometry::CLASSIC,
+        };
+        let size = geometry.size();
+        let mut sudoku = Sudoku::with_geometry(geometry);
+
+        for data in content.split(";") {
+            match section_identifier(data) {
+                "" | SIZE_IDENTIFIER => continue,
+                FIELD_IDENTIFIER => {
+                    let mut count = 0;
+                    for c in section_content(data, FIELD_IDENTIFIER).chars() {
+                        if count == size * size {
+                            break;
+                        }
+                        if let Some(number) = Geometry::char_to_number(c) {
+                            if number as usize > size {
+                                return Err(invalid_section(
+                                    FIELD_IDENTIFIER,
+                                    format!("{} is too big for a Sudoku of size {}", c, size),
+                                ));
+                            }
+                            sudoku.add_given(count % size, count / size, number);
+                            count += 1;
+                        }
                     }
-                    if c >= '0' && c <= '9' {
-                        sudoku.add_number(
-                            count % 9,
-                            count / 9,
-                            c.to_digit(10)
-                                .expect("Internal Conversion Error") // This should not happen as we first check c to be confertable
-                                .try_into()
-                                .unwrap(),
-                        );
-                        count += 1;
+                }
+                CANDIDATES_IDENTIFIER => {
+                    let (candidates_geometry, candidates) =
+                        pencil_marks::parse_pencil_marks(section_content(data, CANDIDATES_IDENTIFIER))?;
+                    if candidates_geometry.size() != size {
+                        return Err(invalid_section(
+                            CANDIDATES_IDENTIFIER,
+                            format!("There are candidates for a Sudoku of size {}", candidates_geometry.size()),
+                        ));
                     }
+                    sudoku.set_candidates(&candidates);
+                }
+                _ => sudoku.add_section(data, registry)?,
+            }
+        }
+        Ok(sudoku)
+    }
+
+    /// Adds the rules of a section. The section is kept to write it again.
+    pub(crate) fn add_section(&mut self, data: &str, registry: &RuleRegistry) -> Result<(), ParseError> {
+        let identifier = section_identifier(data);
+        let constructors = registry
+            .constructors(identifier)
+            .ok_or_else(|| ParseError::UnknownSection(identifier.to_string()))?;
+        for constructor in constructors {
+            self.add_rule(constructor(data, self.geometry)?);
+        }
+
+        self.sections.push(data.trim().to_string());
+        Ok(())
+    }
+
+    /// Returns an Sudoku from a puzzle of f-puzzles or SudokuPad.
+    ///
+    /// The input is the JSON of the puzzle, its compressed form or a link like
+    /// `https://www.f-puzzles.com/?load=...` or `https://sudokupad.app/fpuzzles...`.
+    /// Constraints without a rule in this crate are an error.
+    /// ```
+    /// use sudoku::sudokus::{ParseError, Sudoku};
+    ///
+    /// let puzzle = r#"{"size":4,"grid":[[{"value":1,"given":true},{},{},{}],[{},{},{},{}],[{},{},{},{}],[{},{},{},{}]]}"#;
+    /// assert_eq!(Sudoku::from_f_puzzles(puzzle).unwrap().get_number(0, 0), 1);
+    ///
+    /// let puzzle = r#"{"size":9,"clone":[{"cells":["R1C1"],"cloneCells":["R9C9"]}]}"#;
+    /// assert_eq!(Sudoku::from_f_puzzles(puzzle).err(), Some(ParseError::Unsupported("clone".to_string())));
+    /// ```
+    pub fn from_f_puzzles(input: &str) -> Result<Self, ParseError> {
+        Sudoku::parse_with_registry(&f_puzzles::to_sections(input)?, &RuleRegistry::default())
+    }
+
+    /// Returns the Sudoku for the terminal with borders, colors and the statistics of the moves.
+    /// The options are set on the returned `Pretty`, without options it is the same as `{:#}`.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let mut sudoku = Sudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved");
+    /// sudoku.set_missing_numbers();
+    /// println!("{}", sudoku.pretty().candidates(true));
+    /// ```
+    pub fn pretty(&self) -> Pretty<'_> {
+        Pretty::new(self)
+    }
+
+    /// Returns an Sudoku from a PNG picture of a classic Sudoku, like a screenshot of a newspaper.
+    /// All numbers of the picture are givens. See: `./test-sudokus/pics/`
+    ///
+    /// The digits are recognized with the fonts of the pictures there, other fonts may be read wrong.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_picture("test-sudokus/pics/welt-23-11-11-hard-unsolved.png").unwrap();
+    /// assert_eq!(sudoku, Sudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved"));
+    /// ```
+    pub fn from_picture(file_path: &str) -> Result<Self, ParseError> {
+        let numbers = picture::read_picture(file_path)?;
+        let size = Geometry::CLASSIC.size();
+        let mut sudoku = Sudoku::with_geometry(Geometry::CLASSIC);
+
+        for (count, number) in numbers.into_iter().enumerate() {
+            sudoku.add_given(count % size, count / size, number);
+        }
+        Ok(sudoku)
+    }
+
+    /// Returns an Sudoku from a grid with the candidates of every cell.
+    /// For the data structure see the `candidates` section, that can also be part of a file with rules.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let mut sudoku = Sudoku::from_file("test-sudokus/text/welt-23-11-11-medium-unsolved");
+    /// sudoku.set_missing_numbers();
+    ///
+    /// let pencil_marks = sudoku.to_pencil_marks();
+    /// assert_eq!(Sudoku::parse_pencil_marks(&pencil_marks).unwrap(), sudoku);
+    /// ```
+    pub fn parse_pencil_marks(content: &str) -> Result<Self, ParseError> {
+        let (geometry, candidates) = pencil_marks::parse_pencil_marks(content)?;
+        let mut sudoku = Sudoku::with_geometry(geometry);
+        sudoku.set_candidates(&candidates);
+        Ok(sudoku)
+    }
+
+    /// Returns the candidates of every cell in a grid with borders like HoDoKu.
+    /// Cells that haven't been looked at are written as `.`
+    pub fn to_pencil_marks(&self) -> String {
+        let size = self.geometry.size();
+        let candidates: Vec<_> = (0..size * size).map(|count| self.field[count % size][count / size]).collect();
+        pencil_marks::write_pencil_marks(self.geometry, &candidates)
+    }
+
+    /// Sets the candidates of every cell in the raw format, in the same order as the `field` section
+    fn set_candidates(&mut self, candidates: &[u32]) {
+        let size = self.geometry.size();
+        for (count, &candidates) in candidates.iter().enumerate() {
+            self.field[count % size][count / size] = candidates;
+        }
+    }
+
+    /// Returns an Sudoku from a single line with a character for every cell.
+    /// The size is given by the length of the line and unset cells are `0` or `.`.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let line = "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......";
+    /// let sudoku = Sudoku::from_line(line).unwrap();
+    /// assert_eq!(sudoku.get_number(0, 0), 4);
+    /// assert_eq!(sudoku.to_line(), line);
+    ///
+    /// assert!(Sudoku::from_line("4.....8.5").is_err());
+    /// ```
+    pub fn from_line(line: &str) -> Result<Self, ParseError> {
+        let (geometry, numbers) = line_format::parse_line(line)?;
+        let size = geometry.size();
+        let mut sudoku = Sudoku::with_geometry(geometry);
+
+        for (count, number) in numbers.into_iter().enumerate() {
+            sudoku.add_given(count % size, count / size, number);
+        }
+        Ok(sudoku)
+    }
+
+    /// Returns all Sudokus of a file with a Sudoku in every line.
+    /// Empty lines and comments starting with `#` are skipped.
+    ///
+    /// The file is read while iterating, so big collections don't have to fit in memory.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let path = "test-sudokus/lines/collection-26-10-18-unsolved";
+    /// for sudoku in Sudoku::lines_from_file(path).unwrap() {
+    ///     assert!(sudoku.unwrap().is_part_correct());
+    /// }
+    /// ```
+    pub fn lines_from_file(file_path: &str) -> Result<impl Iterator<Item = Result<Self, ParseError>>, ParseError> {
+        Ok(line_format::read_lines(file_path)?.map(|line| Sudoku::from_line(&line?)))
+    }
+
+    /// Returns the numbers of the Sudoku in a single line. Unset cells are written as `.`
+    ///
+    /// Only the numbers are written, the rules are not part of the line.
+    pub fn to_line(&self) -> String {
+        let size = self.geometry.size();
+        line_format::write_line((0..size * size).map(|count| self.get_number(count % size, count / size)))
+    }
+
+    /// Returns an Sudoku from the content of a file of another program like HoDoKu or Simple Sudoku.
+    /// The numbers of the `[State]` section of a SadMan file are set, but they aren't given or moves.
+    /// ```
+    /// use sudoku::sudokus::{FileFormat, Sudoku};
+    ///
+    /// let content = "4..|...|8.5\n.3.|...|...\n...|7..|...\n-----------\n\
+    ///                .2.|...|.6.\n...|.8.|4..\n...|.1.|...\n-----------\n\
+    ///                ...|6.3|.7.\n5..|2..|...\n1.4|...|...\n";
+    /// let sudoku = Sudoku::parse_format(content, FileFormat::Ss).unwrap();
+    /// assert_eq!(sudoku.get_number(6, 0), 8);
+    /// assert_eq!(sudoku.to_format(FileFormat::Ss), content.replace(' ', ""));
+    /// ```
+    pub fn parse_format(content: &str, format: FileFormat) -> Result<Self, ParseError> {
+        let (geometry, numbers, state) = format.parse(content)?;
+        let size = geometry.size();
+        let mut sudoku = Sudoku::with_geometry(geometry);
+
+        for (count, &number) in numbers.iter().enumerate() {
+            sudoku.add_given(count % size, count / size, number);
+        }
+
+        // The state has the given numbers and the numbers the player found.
+        // They were found before the Sudoku was read, so they aren't moves.
+        for (count, number) in state.into_iter().flatten().enumerate() {
+            if numbers[count] == 0 && number != 0 {
+                sudoku.field[count % size][count / size] = 0b1 << (number - 1);
+            }
+        }
+        Ok(sudoku)
+    }
+
+    /// Returns an Sudoku from a file of another program. The format is given by the extension.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_file_with_format("test-sudokus/formats/welt-23-11-11-hard-unsolved.ss");
+    /// assert_eq!(sudoku.unwrap(), Sudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved"));
+    /// ```
+    pub fn from_file_with_format(file_path: &str) -> Result<Self, ParseError> {
+        let format = FileFormat::from_path(file_path)
+            .ok_or_else(|| ParseError::Io(format!("Unknown file extension of {}", file_path)))?;
+        let content = fs::read_to_string(file_path).map_err(|error| ParseError::Io(error.to_string()))?;
+        Sudoku::parse_format(&content, format)
+    }
+
+    /// Returns the numbers of the Sudoku in the format of another program. Rules are not written.
+    ///
+    /// A SadMan file has the given numbers as `[Puzzle]` and the solved numbers as `[State]`.
+    /// The other formats can't tell them apart and have all numbers.
+    /// ```
+    /// use sudoku::sudokus::{FileFormat, Sudoku};
+    ///
+    /// let mut sudoku = Sudoku::from_line("12.4..1.2..1.12.").unwrap();
+    /// sudoku.solve();
+    ///
+    /// assert_eq!(sudoku.to_format(FileFormat::Sdk), "1234\n3412\n2341\n4123\n");
+    /// assert!(sudoku.to_format(FileFormat::SadMan).contains("[Puzzle]\n12.4\n..1.\n2..1\n.12.\n"));
+    /// ```
+    pub fn to_format(&self, format: FileFormat) -> String {
+        let size = self.geometry.size();
+        let numbers: Vec<_> = (0..size * size)
+            .map(|count| self.get_number(count % size, count / size))
+            .collect();
+        match format {
+            FileFormat::SadMan => {
+                let givens: Vec<_> = (0..size * size)
+                    .map(|count| match self.is_given(count % size, count / size) {
+                        true => numbers[count],
+                        false => 0,
+                    })
+                    .collect();
+                format.write(self.geometry, &givens, Some(&numbers))
+            }
+            _ => format.write(self.geometry, &numbers, None),
+        }
+    }
+
+    /// Returns a drawing of the Sudoku with the decorations of all rules.
+    /// Given numbers are black and solved numbers blue. If `candidates` is set, the
+    /// candidates of the cells that aren't set are drawn small.
+    pub fn drawing(&self, candidates: bool) -> Drawing {
+        let size = self.geometry.size();
+        let mut drawing = Drawing::new(self.geometry);
+
+        for rule in &self.rules {
+            rule.draw(&mut drawing);
+        }
+        drawing.grid();
+
+        for collum in 0..size {
+            for row in 0..size {
+                if self.is_set(row, collum) {
+                    drawing.number((row, collum), self.get_number(row, collum), self.is_given(row, collum));
+                } else if candidates && self.field[row][collum] != 0 {
+                    drawing.candidates((row, collum), self.field[row][collum]);
                 }
             }
+        }
+        drawing
+    }
 
-            if data.contains(XSudoku::str_identifier()){
-                sudoku.rules.push(XSudoku::from_str(data));
+    /// Returns a drawing of the Sudoku as SVG. For the drawing see `Sudoku::drawing`
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_file("test-sudokus/text/constructed-26-10-18-killer-hard-unsolved");
+    /// let svg = sudoku.to_svg(false);
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(">15</text>"));
+    /// ```
+    pub fn to_svg(&self, candidates: bool) -> String {
+        self.drawing(candidates).to_svg()
+    }
+
+    /// Adds a rule with its houses and peers
+    fn add_rule(&mut self, rule: Box<dyn SudokuRule>) {
+        let size = self.geometry.size();
+
+        for house in rule.houses(size) {
+            for &(row, collum) in &house {
+                let peers = &mut self.peers[row][collum];
+                peers.extend(house.iter().filter(|&&cell| cell != (row, collum)));
             }
+            self.houses.push(house);
+        }
 
-            if data.contains(EvenOddSudoku::str_identifier()){
-                sudoku.rules.push(EvenOddSudoku::from_str(data));
+        for row in 0..size {
+            for collum in 0..size {
+                let peers = rule.peers(row, collum, size);
+                self.peers[row][collum].extend(peers);
             }
         }
-        return sudoku;
+
+        self.rules.push(rule);
+    }
+
+    /// Returns the size of the Sudoku and the shape of its boxes
+    /// ```
+    /// use sudoku::sudokus::{Geometry, Sudoku};
+    ///
+    /// let sudoku = Sudoku::example_solved();
+    /// assert_eq!(sudoku.geometry(), Geometry::CLASSIC);
+    /// ```
+    pub fn geometry(&self) -> Geometry {
+        self.geometry
     }
 
     /// Returns the number of already taken moves
@@ -225,8 +725,8 @@ impl Sudoku {
     /// ```
     #[inline]
     pub fn is_set(&self, row: usize, collum: usize) -> bool {
-        debug_assert!(row < 9 && collum < 9);
-        return i32::count_ones(self.field[row][collum]) == 1;
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
+        return u32::count_ones(self.field[row][collum]) == 1;
     }
 
     /// Counts the number of cells in the sudoku that are not set
@@ -240,8 +740,8 @@ impl Sudoku {
     /// ```
     pub fn count_unset(&self) -> u32 {
         let mut count = 0;
-        for row in 0..9 {
-            for collum in 0..9 {
+        for row in 0..self.geometry.size() {
+            for collum in 0..self.geometry.size() {
                 if self.is_set(row, collum) {
                     continue;
                 }
@@ -252,7 +752,31 @@ impl Sudoku {
         return count;
     }
 
-    /// Clears a cell
+    /// Returns if the number of a cell is part of the puzzle and wasn't solved
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let mut sudoku = Sudoku::parse("field: 0 3;");
+    /// sudoku.solve();
+    ///
+    /// assert!(sudoku.is_given(1, 0));
+    /// assert!(!sudoku.is_given(0, 0));
+    /// ```
+    pub fn is_given(&self, row: usize, collum: usize) -> bool {
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
+        self.givens[row][collum]
+    }
+
+    /// Adds a number of the puzzle
+    fn add_given(&mut self, row: usize, collum: usize, value: i32) {
+        if value == 0 {
+            return;
+        }
+        self.add_number(row, collum, value);
+        self.givens[row][collum] = true;
+    }
+
+    /// Clears a cell. Given numbers are not cleared and false is returned.
     /// ```
     /// use sudoku::sudokus::Sudoku;
     ///
@@ -262,13 +786,37 @@ impl Sudoku {
     /// sudoku.clear(0,0);
     ///
     /// assert_eq!(sudoku.get_number(0,0), 0);
+    ///
+    /// let mut sudoku = Sudoku::parse("field: 3;");
+    /// assert!(!sudoku.clear(0, 0));
+    /// assert_eq!(sudoku.get_number(0, 0), 3);
     /// ```
-    pub fn clear(&mut self, row: usize, collum: usize) {
-        debug_assert!(row < 9 && collum < 9);
+    pub fn clear(&mut self, row: usize, collum: usize) -> bool {
+        if self.is_given(row, collum) {
+            return false;
+        }
+        self.force_clear(row, collum);
+        true
+    }
+
+    /// Clears a cell, even if it is given. It isn't given afterwards.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let mut sudoku = Sudoku::parse("field: 3;");
+    /// sudoku.force_clear(0, 0);
+    ///
+    /// assert_eq!(sudoku.get_number(0, 0), 0);
+    /// assert!(!sudoku.is_given(0, 0));
+    /// ```
+    pub fn force_clear(&mut self, row: usize, collum: usize) {
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
         self.field[row][collum] = 0;
+        self.givens[row][collum] = false;
     }
 
-    /// Adds the number of cell
+    /// Adds the number of cell.
+    /// Nothing is added to given numbers and false is returned.
     ///
     /// ```
     /// use sudoku::sudokus::Sudoku;
@@ -282,20 +830,26 @@ impl Sudoku {
     /// sudoku.add_number(0, 0, 9);
     ///
     /// assert_eq!(sudoku.get_number(0,0), 9);
+    ///
+    /// let mut sudoku = Sudoku::parse("field: 3;");
+    /// assert!(!sudoku.add_number(0, 0, 9));
+    /// assert_eq!(sudoku.get_numbers(0, 0), vec![3]);
     /// ```
-    pub fn add_number(&mut self, row: usize, collum: usize, value: i32) {
-        if value == 0 {
-            return;
+    pub fn add_number(&mut self, row: usize, collum: usize, value: i32) -> bool {
+        if value == 0 || self.is_given(row, collum) {
+            return false;
         }
-        debug_assert!(value >= 1 && value <= 9);
-        debug_assert!(row < 9 && collum < 9);
+        debug_assert!(value >= 1 && value <= self.geometry.size() as i32);
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
         self.field[row][collum] |= 0b1 << (value - 1);
+        true
     }
 
     /// Sets the number of a cell
     ///
     /// It behaves just like a clear follwed by
-    /// a single add
+    /// a single add. Given numbers are not
+    /// overwritten and false is returned.
     ///
     /// ```
     /// use sudoku::sudokus::Sudoku;
@@ -306,26 +860,49 @@ impl Sudoku {
     /// sudoku.set_number(0, 0, 9);
     ///
     /// assert_eq!(sudoku.get_number(0,0), 9);
+    ///
+    /// let mut sudoku = Sudoku::parse("field: 3;");
+    /// assert!(!sudoku.set_number(0, 0, 9));
+    /// assert_eq!(sudoku.get_number(0, 0), 3);
+    /// ```
+    pub fn set_number(&mut self, row: usize, collum: usize, value: i32) -> bool {
+        if value == 0 || self.is_given(row, collum) {
+            return false;
+        }
+        self.force_set_number(row, collum, value);
+        true
+    }
+
+    /// Sets the number of a cell, even if it is given. It isn't given afterwards.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let mut sudoku = Sudoku::parse("field: 3;");
+    /// sudoku.force_set_number(0, 0, 9);
+    ///
+    /// assert_eq!(sudoku.get_number(0, 0), 9);
+    /// assert!(!sudoku.is_given(0, 0));
     /// ```
-    pub fn set_number(&mut self, row: usize, collum: usize, value: i32) {
+    pub fn force_set_number(&mut self, row: usize, collum: usize, value: i32) {
         if value == 0 {
             return;
         }
-        debug_assert!(value >= 1 && value <= 9);
-        debug_assert!(row < 9 && collum < 9);
+        debug_assert!(value >= 1 && value <= self.geometry.size() as i32);
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
 
         self.moves.player_moves += 1;
         self.field[row][collum] = 0b1 << (value - 1);
+        self.givens[row][collum] = false;
     }
 
     /// Brutforces the next number
     /// Due to the implementation it solves the sudoku in the progress and returns true
     /// if it is sovable. If it isn't it returns false.
     fn brute_force(&mut self) -> bool {
-        let mut row_to_check = 9;
-        let mut collum_to_check = 9;
-        'findfield: for row in 0..9 {
-            for collum in 0..9 {
+        let mut row_to_check = self.geometry.size();
+        let mut collum_to_check = self.geometry.size();
+        'findfield: for row in 0..self.geometry.size() {
+            for collum in 0..self.geometry.size() {
                 if !self.is_set(row, collum) {
                     row_to_check = row;
                     collum_to_check = collum;
@@ -334,7 +911,7 @@ impl Sudoku {
                 }
             }
         }
-        if row_to_check == 9 || collum_to_check == 9 {
+        if row_to_check == self.geometry.size() || collum_to_check == self.geometry.size() {
             return false;
         }
 
@@ -344,7 +921,7 @@ impl Sudoku {
 
         let numbers_to_check = !self.get_not_possible_numbers_raw(row_to_check, collum_to_check);
 
-        for num in 0..9 {
+        for num in 0..self.geometry.size() {
             if (0b1 << num) & numbers_to_check == 0 {
                 continue;
             }
@@ -359,7 +936,7 @@ impl Sudoku {
 
             self.moves.failed_alone_moves += self.moves.alone_moves - alone_moves;
             self.moves.failed_missing_moves += self.moves.missing_moves - missing_moves;
-            self.field = field;
+            self.field.clone_from(&field);
             self.moves.missing_moves = missing_moves;
             self.moves.alone_moves = alone_moves;
             self.moves.bruteforce_failed_tries += 1;
@@ -410,17 +987,22 @@ impl Sudoku {
         return true;
     }
 
-    /// Removes a number from a field
-    pub fn remove_number(&mut self, row: usize, collum: usize, value: i32) {
-        debug_assert!(value >= 1 && value <= 9);
-        debug_assert!(row < 9 && collum < 9);
+    /// Removes a number from a field.
+    /// Given numbers are not removed and false is returned.
+    pub fn remove_number(&mut self, row: usize, collum: usize, value: i32) -> bool {
+        debug_assert!(value >= 1 && value <= self.geometry.size() as i32);
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
+        if self.is_given(row, collum) {
+            return false;
+        }
         self.field[row][collum] &= !(0b1 << (value - 1));
+        true
     }
 
     /// Returns the number of a cell
     /// If no or multible numbers are set it returns 0
     pub fn get_number(&self, row: usize, collum: usize) -> i32 {
-        debug_assert!(row < 9 && collum < 9);
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
         let numbers = self.get_numbers(row, collum);
 
         if numbers.len() != 1 {
@@ -432,11 +1014,11 @@ impl Sudoku {
 
     /// Gets all numbers that are set in a cell as a Vec
     pub fn get_numbers(&self, row: usize, collum: usize) -> Vec<i32> {
-        debug_assert!(row < 9 && collum < 9);
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
         let number = self.field[row][collum];
         let mut vec = Vec::new();
 
-        for i in 0..9 {
+        for i in 0..self.geometry.size() as i32 {
             if number & (0b1 << i) == 0b1 << i {
                 vec.push(i + 1);
             }
@@ -455,9 +1037,9 @@ impl Sudoku {
     /// ```
     #[inline]
     pub fn is_solved(&self) -> bool {
-        for row in 0..9 {
-            for collum in 0..9 {
-                if i32::count_ones(self.field[row][collum]) != 1 {
+        for row in 0..self.geometry.size() {
+            for collum in 0..self.geometry.size() {
+                if u32::count_ones(self.field[row][collum]) != 1 {
                     return false;
                 }
             }
@@ -479,14 +1061,14 @@ impl Sudoku {
     pub fn is_correct(&self) -> bool {
         //return self.is_solved() && self.is_part_correct();
 
-        for i in 0..9 {
+        for i in 0..self.geometry.size() {
             let mut row_nums = 0;
             let mut collum_nums = 0;
-            let mut square_nums = 0;
-            for j in 0..9 {
+            let mut box_nums = 0;
+            for (j, (box_row, box_collum)) in self.geometry.box_cells(i).enumerate() {
                 if row_nums & self.field[i][j] != 0
                     || collum_nums & self.field[j][i] != 0
-                    || square_nums & self.field[i / 3 * 3 + j / 3][i % 3 * 3 + j % 3] != 0
+                    || box_nums & self.field[box_row][box_collum] != 0
                     || self.field[i][j] == 0
                 {
                     return false;
@@ -494,10 +1076,14 @@ impl Sudoku {
 
                 row_nums |= self.field[i][j];
                 collum_nums |= self.field[j][i];
-                square_nums |= self.field[i / 3 * 3 + j / 3][i % 3 * 3 + j % 3];
+                box_nums |= self.field[box_row][box_collum];
             }
         }
 
+        if !self.is_peer_part_correct() {
+            return false;
+        }
+
         for rule in &self.rules {
             if !rule.complys(&self.field) {
                 return false;
@@ -511,12 +1097,12 @@ impl Sudoku {
     ///
     /// It doesn't check if the sudoku has a solution.
     #[inline]
-    fn is_solveable(&self) -> bool {
-        for row in 0..9 {
-            for collum in 0..9 {
+    pub(crate) fn is_solveable(&self) -> bool {
+        for row in 0..self.geometry.size() {
+            for collum in 0..self.geometry.size() {
                 if !self.is_set(row, collum)
-                    && self.get_not_possible_numbers_raw(row, collum) & 0b111_111_111
-                        == 0b111_111_111
+                    && self.get_not_possible_numbers_raw(row, collum) & self.geometry.all()
+                        == self.geometry.all()
                 {
                     return false;
                 }
@@ -535,15 +1121,19 @@ impl Sudoku {
     #[inline]
     pub fn is_part_correct(&self) -> bool {
         // check rows and collums
-        for i in 0..9 {
+        for i in 0..self.geometry.size() {
             if !self.is_collum_part_correct(i)
                 || !self.is_row_part_correct(i)
-                || !self.is_square_part_correct(i / 3, i % 3)
+                || !self.is_box_part_correct(i)
             {
                 return false;
             }
         }
 
+        if !self.is_peer_part_correct() {
+            return false;
+        }
+
         for rule in &self.rules {
             if !rule.complys(&self.field) {
                 return false;
@@ -553,30 +1143,84 @@ impl Sudoku {
         return true;
     }
 
-    /// Returns true if a 3x3 square is correct
-    fn is_square_part_correct(&self, big_row: usize, big_collum: usize) -> bool {
-        debug_assert!(big_row < 3 && big_collum < 3);
-        let mut set_numbers = 0;
-        for row in 0..3 {
-            for collum in 0..3 {
-                if !self.is_set(big_row * 3 + row, big_collum * 3 + collum) {
+    /// Returns the set cells with the same number as another cell of their row, collum, box or peers.
+    /// Other errors of the rules, like a wrong sum of an arrow, are not part of it.
+    /// ```
+    /// use sudoku::sudokus::Sudoku;
+    ///
+    /// let sudoku = Sudoku::from_line("1..1.2..........").unwrap();
+    /// assert!(!sudoku.is_part_correct());
+    /// assert_eq!(sudoku.conflicts(), vec![(0, 0), (3, 0)]);
+    ///
+    /// assert!(Sudoku::example_solved().conflicts().is_empty());
+    /// ```
+    pub fn conflicts(&self) -> Vec<(usize, usize)> {
+        let size = self.geometry.size();
+        let mut conflicts = vec![];
+        for collum in 0..size {
+            for row in 0..size {
+                if !self.is_set(row, collum) {
                     continue;
                 }
-                if set_numbers & self.field[big_row * 3 + row][big_collum * 3 + collum] != 0 {
-                    return false;
+
+                let box_cells = self.geometry.box_cells(self.geometry.box_index(row, collum));
+                let conflict = (0..size)
+                    .map(|i| (i, collum))
+                    .chain((0..size).map(|i| (row, i)))
+                    .chain(box_cells)
+                    .chain(self.peers[row][collum].iter().copied())
+                    .any(|(peer_row, peer_collum)| {
+                        (peer_row, peer_collum) != (row, collum)
+                            && self.field[peer_row][peer_collum] == self.field[row][collum]
+                    });
+                if conflict {
+                    conflicts.push((row, collum));
                 }
+            }
+        }
+        conflicts
+    }
+
+    /// Returns true if no set cell has the same number as one of its extra peers
+    fn is_peer_part_correct(&self) -> bool {
+        for row in 0..self.geometry.size() {
+            for collum in 0..self.geometry.size() {
+                if !self.is_set(row, collum) {
+                    continue;
+                }
+
+                for &(peer_row, peer_collum) in &self.peers[row][collum] {
+                    if self.field[peer_row][peer_collum] == self.field[row][collum] {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
 
-                set_numbers |= self.field[big_row * 3 + row][big_collum * 3 + collum];
+    /// Returns true if a box is correct
+    fn is_box_part_correct(&self, index: usize) -> bool {
+        debug_assert!(index < self.geometry.size());
+        let mut set_numbers = 0;
+        for (row, collum) in self.geometry.box_cells(index) {
+            if !self.is_set(row, collum) {
+                continue;
             }
+            if set_numbers & self.field[row][collum] != 0 {
+                return false;
+            }
+
+            set_numbers |= self.field[row][collum];
         }
         return true;
     }
 
     /// Returns true if row is correct
     fn is_row_part_correct(&self, row: usize) -> bool {
-        debug_assert!(row < 9);
+        debug_assert!(row < self.geometry.size());
         let mut set_numbers = 0;
-        for collum in 0..9 {
+        for collum in 0..self.geometry.size() {
             if !self.is_set(row, collum) {
                 continue;
             }
@@ -592,9 +1236,9 @@ impl Sudoku {
 
     /// Returns true if collum is correct
     fn is_collum_part_correct(&self, collum: usize) -> bool {
-        debug_assert!(collum < 9);
+        debug_assert!(collum < self.geometry.size());
         let mut set_numbers = 0;
-        for row in 0..9 {
+        for row in 0..self.geometry.size() {
             if !self.is_set(row, collum) {
                 continue;
             }
@@ -617,8 +1261,8 @@ impl Sudoku {
     /// ```
     pub fn set_missing_numbers(&mut self) -> bool {
         let mut number_set = false;
-        for row in 0..9 {
-            for collum in 0..9 {
+        for row in 0..self.geometry.size() {
+            for collum in 0..self.geometry.size() {
                 if self.is_set(row, collum) {
                     continue;
                 }
@@ -633,39 +1277,47 @@ impl Sudoku {
         return number_set;
     }
 
-    /// Clears and adds the missing numbers in a cell
+    /// Sets the missing numbers in a cell. Candidates that were already removed stay removed.
     /// If a single number is set, it returns true
     #[inline]
     fn set_missing_number(&mut self, row: usize, collum: usize) -> bool {
-        debug_assert!(row < 9 && collum < 9);
+        debug_assert!(row < self.geometry.size() && collum < self.geometry.size());
 
-        let possible = 0b111_111_111 & !self.get_not_possible_numbers_raw(row, collum);
+        let mut possible = self.geometry.all() & !self.get_not_possible_numbers_raw(row, collum);
 
+        // An empty cell hasn't been looked at yet
+        if self.field[row][collum] != 0 {
+            possible &= self.field[row][collum];
+        }
         self.field[row][collum] = possible;
 
-        return i32::count_ones(possible) == 1;
+        return u32::count_ones(possible) == 1;
     }
 
     /// Returns the numbers of cell that are not possible to set in the raw format
     #[inline]
-    fn get_not_possible_numbers_raw(&self, _row: usize, _collum: usize) -> i32 {
-        debug_assert!(_row < 9 && _collum < 9);
+    pub(crate) fn get_not_possible_numbers_raw(&self, _row: usize, _collum: usize) -> u32 {
+        debug_assert!(_row < self.geometry.size() && _collum < self.geometry.size());
         let mut set = 0;
 
-        for i in 0..9 {
-            if _row != i && i32::count_ones(self.field[i][_collum]) == 1 {
+        for i in 0..self.geometry.size() {
+            if _row != i && u32::count_ones(self.field[i][_collum]) == 1 {
                 set |= self.field[i][_collum];
             }
 
-            if _collum != i && i32::count_ones(self.field[_row][i]) == 1 {
+            if _collum != i && u32::count_ones(self.field[_row][i]) == 1 {
                 set |= self.field[_row][i];
             }
+        }
 
-            if _row / 3 * 3 + i / 3 != _row
-                && _collum / 3 * 3 + i % 3 != _collum
-                && i32::count_ones(self.field[_row / 3 * 3 + i / 3][_collum / 3 * 3 + i % 3]) == 1
-            {
-                set |= self.field[_row / 3 * 3 + i / 3][_collum / 3 * 3 + i % 3];
+        for (row, collum) in self.geometry.box_cells(self.geometry.box_index(_row, _collum)) {
+            if row != _row && collum != _collum && u32::count_ones(self.field[row][collum]) == 1 {
+                set |= self.field[row][collum];
+            }
+        }
+        for &(row, collum) in &self.peers[_row][_collum] {
+            if u32::count_ones(self.field[row][collum]) == 1 {
+                set |= self.field[row][collum];
             }
         }
         for rule in &self.rules {
@@ -676,7 +1328,7 @@ impl Sudoku {
 
     /// Sets the first alone number
     /// A alone number is a number that only appears once
-    /// in a row, collum or 3x3 square in the possible numbers
+    /// in a row, collum, box or house of a rule in the possible numbers
     ///
     /// The function returns true, if it was able to set a
     /// alone number
@@ -687,15 +1339,21 @@ impl Sudoku {
     /// sudoku.set_alone_number();
     /// ```
     pub fn set_alone_number(&mut self) -> bool {
-        for i in 0..9 {
+        for i in 0..self.geometry.size() {
             if self.set_alone_number_row(i)
                 || self.set_alone_number_collum(i)
-                || self.set_alone_number_square(i / 3, i % 3)
+                || self.set_alone_number_box(i)
             {
                 return true;
             }
         }
 
+        for index in 0..self.houses.len() {
+            if self.set_alone_number_house(index) {
+                return true;
+            }
+        }
+
         return false;
     }
 
@@ -704,10 +1362,10 @@ impl Sudoku {
     /// The function returns true, if it was able to set a
     /// alone number
     fn set_alone_number_row(&mut self, row: usize) -> bool {
-        'numloop: for num in 0..9 {
+        'numloop: for num in 0..self.geometry.size() {
             let mut appearances = 0;
-            let mut alone_collum = 9;
-            for collum in 0..9 {
+            let mut alone_collum = self.geometry.size();
+            for collum in 0..self.geometry.size() {
                 if !self.is_set(row, collum)
                     && ((0b1 << num) & !self.get_not_possible_numbers_raw(row, collum)) != 0
                 {
@@ -723,7 +1381,7 @@ impl Sudoku {
             if appearances != 1 {
                 continue;
             }
-            debug_assert!(alone_collum < 9);
+            debug_assert!(alone_collum < self.geometry.size());
 
             self.field[row][alone_collum] = 0b1 << num;
             self.moves.alone_moves += 1;
@@ -737,10 +1395,10 @@ impl Sudoku {
     /// The function returns true, if it was able to set a
     /// alone number
     fn set_alone_number_collum(&mut self, collum: usize) -> bool {
-        'numloop: for num in 0..9 {
+        'numloop: for num in 0..self.geometry.size() {
             let mut appearances = 0;
-            let mut alone_row = 9;
-            for row in 0..9 {
+            let mut alone_row = self.geometry.size();
+            for row in 0..self.geometry.size() {
                 if !self.is_set(row, collum)
                     && ((0b1 << num) & !self.get_not_possible_numbers_raw(row, collum)) != 0
                 {
@@ -755,7 +1413,7 @@ impl Sudoku {
             if appearances != 1 {
                 continue;
             }
-            debug_assert!(alone_row < 9);
+            debug_assert!(alone_row < self.geometry.size());
 
             self.field[alone_row][collum] = 0b1 << num;
             self.moves.alone_moves += 1;
@@ -764,41 +1422,34 @@ impl Sudoku {
         return false;
     }
 
-    /// Sets the first alone number in the square big_row big_collum
+    /// Sets the first alone number in the box with the index
     ///
     /// The function returns true, if it was able to set a
     /// alone number
-    fn set_alone_number_square(&mut self, big_row: usize, big_collum: usize) -> bool {
-        'numloop: for num in 0..9 {
+    fn set_alone_number_box(&mut self, index: usize) -> bool {
+        'numloop: for num in 0..self.geometry.size() {
             let mut appearances = 0;
-            let mut alone_row = 9;
-            let mut alone_collum = 9;
-            for row in 0..3 {
-                for collum in 0..3 {
-                    if !self.is_set(big_row * 3 + row, big_collum * 3 + collum)
-                        && ((0b1 << num)
-                            & !self.get_not_possible_numbers_raw(
-                                big_row * 3 + row,
-                                big_collum * 3 + collum,
-                            ))
-                            != 0
-                    {
-                        appearances += 1;
-
-                        if appearances >= 2 {
-                            continue 'numloop;
-                        }
+            let mut alone_row = self.geometry.size();
+            let mut alone_collum = self.geometry.size();
+            for (row, collum) in self.geometry.box_cells(index) {
+                if !self.is_set(row, collum)
+                    && ((0b1 << num) & !self.get_not_possible_numbers_raw(row, collum)) != 0
+                {
+                    appearances += 1;
 
-                        alone_collum = big_collum * 3 + collum;
-                        alone_row = big_row * 3 + row;
+                    if appearances >= 2 {
+                        continue 'numloop;
                     }
+
+                    alone_collum = collum;
+                    alone_row = row;
                 }
             }
 
             if appearances != 1 {
                 continue;
             }
-            debug_assert!(alone_row < 9 && alone_collum < 9);
+            debug_assert!(alone_row < self.geometry.size() && alone_collum < self.geometry.size());
 
             self.field[alone_row][alone_collum] = 0b1 << num;
             self.moves.alone_moves += 1;
@@ -806,11 +1457,49 @@ impl Sudoku {
         }
         return false;
     }
+
+    /// Sets the first alone number in the house of a rule with the index.
+    /// Only houses that have to contain every number are used.
+    ///
+    /// The function returns true, if it was able to set a
+    /// alone number
+    fn set_alone_number_house(&mut self, index: usize) -> bool {
+        if self.houses[index].len() != self.geometry.size() {
+            return false;
+        }
+
+        'numloop: for num in 0..self.geometry.size() {
+            let mut appearances = 0;
+            let mut alone_cell = None;
+            for &(row, collum) in &self.houses[index] {
+                if !self.is_set(row, collum)
+                    && ((0b1 << num) & !self.get_not_possible_numbers_raw(row, collum)) != 0
+                {
+                    appearances += 1;
+
+                    if appearances >= 2 {
+                        continue 'numloop;
+                    }
+
+                    alone_cell = Some((row, collum));
+                }
+            }
+
+            let Some((row, collum)) = alone_cell else {
+                continue;
+            };
+
+            self.field[row][collum] = 0b1 << num;
+            self.moves.alone_moves += 1;
+            return true;
+        }
+        false
+    }
 }
 
 #[cfg(test)]
 mod sudoku_test {
-    use crate::sudokus::Sudoku;
+    use crate::sudokus::{FileFormat, Geometry, ParseError, RuleRegistry, Sudoku};
 
     #[test]
     fn set_and_get() {
@@ -892,6 +1581,45 @@ mod sudoku_test {
         assert_eq!(sudoku_check, sudoku_from_file);
     }
 
+    #[test]
+    fn invalid_sections() {
+        let registry = RuleRegistry::default();
+        let invalid = [
+            ("size: 99; field: 0;", "size"),
+            ("size: 4; field: 5;", "field"),
+            ("killer:\nabc r1c1;", "killer"),
+            ("arrow:\nr1c1 x;", "arrow"),
+            ("arrow:\nr1c1;", "arrow"),
+            ("thermo:\nr1c1 rXc2;", "thermo"),
+            ("little-killer:\nfoo;", "little-killer"),
+            ("kropki:\nfoo;", "kropki"),
+            ("kropki:\nwhite: r1c1-r1c3;", "kropki"),
+            ("xv:\nw: r1c1-r1c2;", "xv"),
+            ("extra-regions:\nfoo;", "extra-regions"),
+            ("lines:\nrenban: r1c1 r1c2 r1c3 r1c4 r1c5 r1c6 r1c7 r1c8 r1c9 r2c9;", "lines"),
+            ("lines:\nwhisper: r1c1 foo;", "lines"),
+            ("lines:\nrenban: r1c1 r1c2 r1c1;", "lines"),
+            ("lines:\nthermo: r1c1 r1c2;", "lines"),
+            ("lines:\nr1c1 r1c2;", "lines"),
+            ("skyscraper:\ntop: 1 2 3;", "skyscraper"),
+            ("skyscraper:\nfoo: - - - - - - - - -;", "skyscraper"),
+            ("sandwich:\nrows: 100 - - - - - - - -;", "sandwich"),
+            ("sandwich:\nfoo bar;", "sandwich"),
+            ("skyscraper:\nnonsense;", "skyscraper"),
+            ("x-sudoku: garbage;", "x-sudoku"),
+            ("anti-knight: r1c1;", "anti-knight"),
+            ("non-consecutive: 1;", "non-consecutive"),
+            ("disjoint-groups: foo;", "disjoint-groups"),
+        ];
+
+        for (content, section) in invalid {
+            match Sudoku::parse_with_registry(content, &registry) {
+                Err(ParseError::InvalidSection { identifier, .. }) => assert_eq!(identifier, section),
+                other => panic!("{} is read as {:?}", content, other.map(|_| ())),
+            }
+        }
+    }
+
     #[test]
     fn correct() {
         let mut sudoku = Sudoku::from_file("test-sudokus/text/tagesspiegel-23-11-11-easy-unsolved");
@@ -961,6 +1689,16 @@ mod sudoku_test {
         while !medium_sudoku.is_correct() && medium_sudoku.set_missing_numbers() {
             assert!(medium_sudoku.is_part_correct());
         }
+
+        // Candidates that were removed before aren't added again
+        let mut sudoku = Sudoku::parse_pencil_marks(
+            "234 1234 1234 1234\n1234 1234 1234 1234\n1234 1234 1234 1234\n1234 1234 1234 12",
+        )
+        .unwrap();
+        sudoku.set_missing_numbers();
+        assert_eq!(sudoku.get_numbers(0, 0), vec![2, 3, 4]);
+        assert_eq!(sudoku.get_numbers(3, 3), vec![1, 2]);
+        assert_eq!(sudoku.get_numbers(1, 0), vec![1, 2, 3, 4]);
     }
 
     #[test]
@@ -1016,6 +1754,71 @@ mod sudoku_test {
         }
     }
 
+    #[test]
+    fn rule_houses() {
+        let mut sudoku = Sudoku::parse("field: 0; x-sudoku;");
+        assert_eq!(sudoku.houses.len(), 2);
+
+        // A number on a diagonal isn't possible in the other cells of the diagonal
+        sudoku.set_number(2, 2, 5);
+        assert_ne!(sudoku.get_not_possible_numbers_raw(7, 7) & 0b10_000, 0);
+        assert_eq!(sudoku.get_not_possible_numbers_raw(7, 6) & 0b10_000, 0);
+
+        sudoku.set_number(7, 7, 5);
+        assert!(!sudoku.is_part_correct());
+
+        // Only the last cell of the diagonal can be a 1
+        let mut sudoku = Sudoku::parse("field: 0; x-sudoku;");
+        sudoku.set_number(1, 0, 1);
+        sudoku.set_number(4, 3, 1);
+        sudoku.set_number(6, 2, 1);
+        sudoku.set_number(0, 7, 1);
+        assert!(sudoku.set_alone_number_house(0));
+        assert_eq!(sudoku.get_number(8, 8), 1);
+    }
+
+    #[test]
+    fn givens() {
+        let mut sudoku = Sudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved");
+        let givens = 81 - sudoku.count_unset();
+        assert!(sudoku.solve());
+
+        let count = (0..81).filter(|count| sudoku.is_given(count % 9, count / 9)).count();
+        assert_eq!(count as u32, givens);
+        assert!(sudoku.is_given(0, 0));
+        assert!(!sudoku.is_given(1, 0));
+
+        // Only solved numbers can be changed
+        assert!(!sudoku.set_number(0, 0, 2));
+        assert!(!sudoku.add_number(0, 0, 2));
+        assert!(!sudoku.remove_number(0, 0, 1));
+        assert!(sudoku.is_set(0, 0));
+        assert!(!sudoku.clear(0, 0));
+        assert!(sudoku.clear(1, 0));
+        assert!(!sudoku.is_correct());
+
+        sudoku.force_set_number(0, 0, 2);
+        assert!(!sudoku.is_given(0, 0));
+        assert!(sudoku.set_number(0, 0, 1));
+    }
+
+    #[test]
+    fn other_sizes() {
+        let mut sudoku = Sudoku::from_file("test-sudokus/text/constructed-26-10-18-16x16-hard-unsolved");
+        assert_eq!(sudoku.geometry().size(), 16);
+        assert_eq!(sudoku.get_number(5, 0), 15);
+
+        assert!(sudoku.solve());
+        assert!(sudoku.is_correct());
+        assert!(sudoku.to_line().starts_with("52BD3FA7C64E8G91"));
+        assert!(sudoku.to_string().starts_with("size: 16;\n\nfield:\n"));
+
+        let sudoku = Sudoku::from_file("test-sudokus/text/constructed-26-10-18-6x6-medium-solved");
+        assert_eq!(sudoku.geometry().box_width(), 3);
+        assert_eq!(sudoku.geometry().box_height(), 2);
+        assert!(sudoku.is_correct());
+    }
+
     #[test]
     fn count_moves() {
         for path in Sudoku::UNSOLVED_SUDOKU_FILES {
@@ -1028,4 +1831,66 @@ mod sudoku_test {
             assert_eq!(unset, sudoku.count_moves());
         }
     }
+
+    #[test]
+    fn sad_man_state() {
+        let content = "[Puzzle]\n12.4\n..1.\n2..1\n.12.\n[State]\n1234\n..1.\n2..1\n.12.\n";
+        let sudoku = Sudoku::parse_format(content, FileFormat::SadMan).unwrap();
+
+        // The numbers of the state are neither given nor moves
+        assert_eq!(sudoku.get_number(2, 0), 3);
+        assert!(!sudoku.is_given(2, 0));
+        assert_eq!(sudoku.moves.player_moves, 0);
+    }
+
+    #[test]
+    fn display() {
+        for path in Sudoku::UNSOLVED_SUDOKU_FILES {
+            let mut sudoku = Sudoku::from_file(path);
+            let content = sudoku.to_string();
+            let again = Sudoku::parse(&content);
+            assert_eq!(again, sudoku);
+            assert_eq!(again.sections, sudoku.sections);
+            assert_eq!(again.to_string(), content);
+
+            // The candidates are written too
+            sudoku.set_missing_numbers();
+            assert_eq!(Sudoku::parse(&sudoku.to_string()), sudoku);
+        }
+
+        // Solved numbers stay apart from the given numbers
+        let mut sudoku = Sudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved");
+        assert!(sudoku.solve());
+        let again = Sudoku::parse(&sudoku.to_string());
+        assert_eq!(again, sudoku);
+        let givens = (0..81).filter(|count| again.is_given(count % 9, count / 9)).count();
+        assert_eq!(givens, 26);
+        assert!(again.to_string().starts_with("field:\n1 0 0  0 9 0  2 3 0\n"));
+        assert!(again.to_string().contains("\ncandidates:\n"));
+
+        let sudoku = Sudoku::from_file("test-sudokus/text/constructed-26-10-18-6x6-medium-unsolved");
+        assert!(sudoku.to_string().starts_with("size: 6;\n\nfield:\n0 0 0  1 0 6\n0 5 6  0 0 4\n\n0 0 0"));
+
+        // Boxes that aren't the usual ones for the size
+        let geometry = Geometry::new(6, 2, 3).unwrap();
+        let sudoku = Sudoku::with_geometry(geometry);
+        assert!(sudoku.to_string().starts_with("size: 6\nbox: 2x3;\n\nfield:\n0 0  0 0  0 0\n"));
+        assert_eq!(Sudoku::parse(&sudoku.to_string()).geometry(), geometry);
+
+        let sudoku = Sudoku::from_file("test-sudokus/text/sudoku-com-24-01-06-x-sudoku-easy-unsolved");
+        assert!(sudoku.to_string().ends_with("0 0 6  1 8 0  0 0 9;\n\nx-sudoku;\n"));
+        assert!(!sudoku.to_string().contains("Moves"));
+    }
+
+    #[test]
+    fn debug() {
+        let mut sudoku = Sudoku::from_file("test-sudokus/text/sudoku-com-24-01-06-x-sudoku-easy-unsolved");
+        sudoku.set_missing_numbers();
+        let debug = format!("{:?}", sudoku);
+
+        assert!(debug.starts_with("Sudoku of size 9 with boxes of 3x3\n"));
+        assert!(debug.contains(&sudoku.to_pencil_marks()));
+        assert!(debug.contains("Rules: [x-sudoku]\n"));
+        assert!(debug.contains("Moves: Moves { missing_moves: "));
+    }
 }
@@ -1,10 +1,363 @@
-pub(crate) trait SudokuRule{
+use std::collections::HashMap;
 
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+
+/// A rule of a Sudoku variant.
+///
+/// The field is indexed as `field[row][collum]` and every cell contains its numbers
+/// in the raw format (see `Sudoku`). An empty cell (`0`) hasn't been looked at by the solver yet.
+/// Rules are created from the sections of a file with a `RuleRegistry`.
+pub trait SudokuRule {
+    /// Returns the identifier of the rule
     fn str_identifier() -> &'static str where Self: Sized;
 
-    fn from_str(input: &str) -> Box<Self> where Self: Sized;
+    /// Returns the identifier of the section the rule is read from.
+    /// By default it is the identifier of the rule.
+    fn section_identifier() -> &'static str where Self: Sized {
+        Self::str_identifier()
+    }
+
+    /// Reads the rule from its section and returns an error if the section is invalid.
+    /// The section still starts with its identifier.
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> where Self: Sized;
+
+    /// Reads the rule from its section and panics if the section is invalid
+    fn from_str(input: &str, geometry: Geometry) -> Box<Self> where Self: Sized {
+        Self::try_from_str(input, geometry).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Returns false if the set numbers break the rule
+    fn complys(&self, field: &[Vec<u32>]) -> bool;
+
+    /// Returns the numbers that are not possible in a cell because of the rule in the raw format
+    fn get_not_possible_numbers_raw(&self, _field: &[Vec<u32>], _row: usize, _collum: usize) -> u32 {
+        0
+    }
+
+    /// Returns extra groups of cells that can't contain a number twice, like the diagonals of a X-Sudoku.
+    ///
+    /// The solver uses them like rows, collums and boxes. A group with as many cells
+    /// as the size of the Sudoku has to contain every number once.
+    fn houses(&self, _size: usize) -> Vec<Vec<(usize, usize)>> {
+        vec![]
+    }
+
+    /// Returns extra cells that can't have the same number as the cell, like the cells
+    /// a knight's move away in an Anti-Knight Sudoku.
+    fn peers(&self, _row: usize, _collum: usize, _size: usize) -> Vec<(usize, usize)> {
+        vec![]
+    }
+
+    /// Draws the decorations of the rule, like cages or thermometers.
+    /// Rules without decorations, like an Anti-Knight Sudoku, draw nothing.
+    fn draw(&self, _drawing: &mut Drawing) {}
+}
+
+/// Returns the error of a section with invalid content
+pub(crate) fn invalid_section(identifier: &str, message: impl Into<String>) -> ParseError {
+    ParseError::InvalidSection {
+        identifier: identifier.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Parses a cell written as `r<line>c<column>` (both starting at 1).
+/// Lines and columns are counted as they appear in the `field` section, so
+/// `r2c5` is `field[4][1]`. The returned tuple is `(row, collum)` used to index `field`.
+pub(crate) fn parse_cell(input: &str, size: usize) -> Option<(usize, usize)> {
+    let (line, column) = parse_position(input)?;
+
+    if !(1..=size).contains(&line) || !(1..=size).contains(&column) {
+        return None;
+    }
+
+    Some((column - 1, line - 1))
+}
+
+/// Parses the line and column of `r<line>c<column>` without checking if it is inside the field
+pub(crate) fn parse_position(input: &str) -> Option<(usize, usize)> {
+    let input = input.trim().to_lowercase();
+    let (line, column) = input.strip_prefix('r')?.split_once('c')?;
+
+    Some((line.parse().ok()?, column.parse().ok()?))
+}
+
+/// Parses all cells of a whitespace separated list like `r1c1 r1c2 r2c3`
+pub(crate) fn parse_cells(input: &str, size: usize) -> Option<Vec<(usize, usize)>> {
+    input.split_whitespace().map(|cell| parse_cell(cell, size)).collect()
+}
+
+/// Parses the clues outside of the rows or columns like `13 7 - - 21 13 22 13 0`.
+/// There has to be a clue for every line. Missing clues are written as `-`.
+pub(crate) fn parse_clues(input: &str, size: usize) -> Option<Vec<Option<i32>>> {
+    let clues = input
+        .split_whitespace()
+        .map(|clue| match clue {
+            "-" => Some(None),
+            _ => clue.parse().ok().map(Some),
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if clues.len() != size {
+        return None;
+    }
+    Some(clues)
+}
+
+/// Parses the edge between two orthogonally adjacent cells like `r1c1-r1c2`
+pub(crate) fn parse_edge(input: &str, size: usize) -> Option<((usize, usize), (usize, usize))> {
+    let (first, second) = input.split_once('-')?;
+    let first = parse_cell(first, size)?;
+    let second = parse_cell(second, size)?;
+
+    if first.0.abs_diff(second.0) + first.1.abs_diff(second.1) != 1 {
+        return None;
+    }
+
+    Some((first, second))
+}
+
+/// Returns the cells that share an edge with a cell
+pub(crate) fn orthogonal_neighbours(row: usize, collum: usize, size: usize) -> Vec<(usize, usize)> {
+    let mut neighbours = Vec::with_capacity(4);
+    if row > 0 {
+        neighbours.push((row - 1, collum));
+    }
+    if row + 1 < size {
+        neighbours.push((row + 1, collum));
+    }
+    if collum > 0 {
+        neighbours.push((row, collum - 1));
+    }
+    if collum + 1 < size {
+        neighbours.push((row, collum + 1));
+    }
+    neighbours
+}
+
+/// Returns the cells that are reached from a cell by the offsets and are inside the field
+pub(crate) fn offset_cells(
+    row: usize,
+    collum: usize,
+    offsets: &[(isize, isize)],
+    size: usize,
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    offsets.iter().filter_map(move |&(row_offset, collum_offset)| {
+        let other_row = row.checked_add_signed(row_offset)?;
+        let other_collum = collum.checked_add_signed(collum_offset)?;
+
+        if other_row >= size || other_collum >= size {
+            return None;
+        }
+        Some((other_row, other_collum))
+    })
+}
+
+/// Returns the text of a section after its identifier and the following `:`
+pub(crate) fn section_content<'a>(input: &'a str, identifier: &str) -> &'a str {
+    let after_identifier = match input.find(identifier) {
+        Some(start) => &input[start + identifier.len()..],
+        None => input,
+    };
+
+    after_identifier.trim_start().strip_prefix(':').unwrap_or(after_identifier)
+}
+
+/// Returns an error if the section of a rule without content like `x-sudoku` has content
+pub(crate) fn marker_section(input: &str, identifier: &str) -> Result<(), ParseError> {
+    match section_content(input, identifier).trim() {
+        "" => Ok(()),
+        content => Err(invalid_section(identifier, format!("Unexpected content {}", content))),
+    }
+}
+
+/// Returns the identifier of a section, that is the text in front of the first `:`.
+/// Sections without content like `x-sudoku` are only their identifier.
+pub(crate) fn section_identifier(input: &str) -> &str {
+    input.split(':').next().unwrap_or("").trim()
+}
+
+/// Returns true if no house contains a set number twice
+pub(crate) fn houses_comply(field: &[Vec<u32>], houses: &[Vec<(usize, usize)>]) -> bool {
+    for house in houses {
+        let mut set_numbers = 0;
+        for &(row, collum) in house {
+            if field[row][collum].count_ones() != 1 {
+                continue;
+            }
+            if set_numbers & field[row][collum] != 0 {
+                return false;
+            }
+
+            set_numbers |= field[row][collum];
+        }
+    }
+
+    true
+}
+
+/// Returns all numbers of a field in the raw format
+#[inline]
+pub(crate) fn all_numbers(field: &[Vec<u32>]) -> u32 {
+    (0b1 << field.len()) - 1
+}
+
+/// Returns the numbers that can still be in a cell in the raw format.
+/// An empty cell hasn't been looked at by the solver yet, so all numbers are possible.
+#[inline]
+pub(crate) fn candidates(field: &[Vec<u32>], row: usize, collum: usize) -> u32 {
+    if field[row][collum] == 0 {
+        return all_numbers(field);
+    }
+
+    field[row][collum]
+}
+
+/// A set of sums. The sum `n` is bit `n % 64` of the word `n / 64`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Sums {
+    words: Vec<u64>,
+}
+
+impl Sums {
+    /// Returns true if the sum is part of the set
+    pub(crate) fn contains(&self, sum: i32) -> bool {
+        sum >= 0
+            && self
+                .words
+                .get(sum as usize / 64)
+                .is_some_and(|word| word & (0b1 << (sum % 64)) != 0)
+    }
+
+    /// Adds all sums of the other set increased by `number`
+    fn add_shifted(&mut self, other: &Sums, number: usize) {
+        let (words, bits) = (number / 64, number % 64);
+        if self.words.len() < other.words.len() + words + 1 {
+            self.words.resize(other.words.len() + words + 1, 0);
+        }
+
+        for (i, &word) in other.words.iter().enumerate() {
+            self.words[i + words] |= word << bits;
+            if bits != 0 {
+                self.words[i + words + 1] |= word >> (64 - bits);
+            }
+        }
+    }
+}
+
+/// Returns all sums that can be reached by picking one number out of every
+/// candidate set.
+///
+/// Numbers may repeat, so this is an upper bound for cells that see each other.
+pub(crate) fn reachable_sums(candidates: impl IntoIterator<Item = u32>) -> Sums {
+    let mut sums = Sums { words: vec![0b1] };
+    for candidate in candidates {
+        let mut next = Sums::default();
+        for num in 0..u32::BITS as usize {
+            if candidate & (0b1 << num) != 0 {
+                next.add_shifted(&sums, num + 1);
+            }
+        }
+        sums = next;
+    }
+    sums
+}
+
+/// Returns all sums that can be reached by picking one number out of every
+/// candidate set, if all picked numbers and the `used` numbers are different.
+pub(crate) fn reachable_distinct_sums(candidates: &[u32], used: u32) -> Sums {
+    distinct_sums(candidates, used, &mut HashMap::new())
+}
+
+/// Memoized by the number of left cells and the used numbers
+fn distinct_sums(candidates: &[u32], used: u32, memo: &mut HashMap<(usize, u32), Sums>) -> Sums {
+    let Some((&first, rest)) = candidates.split_first() else {
+        return Sums { words: vec![0b1] };
+    };
+    if let Some(sums) = memo.get(&(candidates.len(), used)) {
+        return sums.clone();
+    }
+
+    let mut sums = Sums::default();
+    for num in 0..u32::BITS as usize {
+        if first & !used & (0b1 << num) != 0 {
+            let others = distinct_sums(rest, used | (0b1 << num), memo);
+            sums.add_shifted(&others, num + 1);
+        }
+    }
+
+    memo.insert((candidates.len(), used), sums.clone());
+    sums
+}
+
+/// Returns the numbers up to `size` in the raw format that are related to at least one of the candidates
+pub(crate) fn related_numbers(candidates: u32, size: usize, related: impl Fn(i32, i32) -> bool) -> u32 {
+    let mut numbers = 0;
+    for num in 1..=size as i32 {
+        for other in 1..=size as i32 {
+            if candidates & (0b1 << (other - 1)) != 0 && related(num, other) {
+                numbers |= 0b1 << (num - 1);
+                break;
+            }
+        }
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod sudoku_rule_test {
+    use super::{
+        parse_cell, parse_clues, parse_edge, reachable_distinct_sums, reachable_sums,
+        related_numbers, section_content, section_identifier,
+    };
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_cell("r1c1", 9), Some((0, 0)));
+        assert_eq!(parse_cell("R2C5", 9), Some((4, 1)));
+        assert_eq!(parse_cell("r0c1", 9), None);
+        assert_eq!(parse_cell("r1c10", 9), None);
+        assert_eq!(parse_cell("r1c10", 16), Some((9, 0)));
+        assert_eq!(parse_cell("x", 9), None);
+
+        assert_eq!(parse_edge("r1c1-r1c2", 9), Some(((0, 0), (1, 0))));
+        assert_eq!(parse_edge("r1c1-r2c2", 9), None);
+
+        assert_eq!(parse_clues("1 - 3 4", 4), Some(vec![Some(1), None, Some(3), Some(4)]));
+        assert_eq!(parse_clues("1 - 3", 4), None);
+
+        assert_eq!(section_content("\n\narrow:\nr1c1 r1c2", "arrow"), "\nr1c1 r1c2");
+        assert_eq!(section_identifier("\n\narrow:\nr1c1 r1c2"), "arrow");
+        assert_eq!(section_identifier("\nx-sudoku\n"), "x-sudoku");
+        assert_eq!(section_identifier("\n"), "");
+    }
+
+    #[test]
+    fn sums() {
+        // {1, 2} + {3} = {4, 5}
+        let sums = reachable_sums([0b11, 0b100]);
+        assert!((0..100).all(|sum| sums.contains(sum) == (sum == 4 || sum == 5)));
+        assert!(reachable_sums([]).contains(0));
+        assert!((0..10).all(|sum| !reachable_sums([0b0, 0b1]).contains(sum)));
+
+        // Sums above 64 are stored in the next words
+        let sums = reachable_sums([0b1 << 24; 6]);
+        assert!(sums.contains(150));
+        assert!(!sums.contains(149));
 
-    fn complys(&self, field: &[[i32; 9]; 9]) -> bool;
+        // {1, 2} + {1, 2} can only be 3 with different numbers
+        let sums = reachable_distinct_sums(&[0b11, 0b11], 0);
+        assert!((0..10).all(|sum| sums.contains(sum) == (sum == 3)));
+        assert!(!reachable_distinct_sums(&[0b11, 0b11], 0b1).contains(3));
+        assert!(reachable_distinct_sums(&[], 0b1).contains(0));
+    }
 
-    fn get_not_possible_numbers_raw(&self, field: &[[i32; 9]; 9], row: usize, collum: usize) -> i32;
-}
\ No newline at end of file
+    #[test]
+    fn related() {
+        // Only 2 and 4 are consecutive to 3
+        assert_eq!(related_numbers(0b100, 9, |a, b| a.abs_diff(b) == 1), 0b1010);
+        assert_eq!(related_numbers(0, 9, |_, _| true), 0);
+    }
+}
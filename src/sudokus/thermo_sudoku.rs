@@ -0,0 +1,174 @@
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{
+    all_numbers, candidates, invalid_section, parse_cells, section_content, SudokuRule,
+};
+
+/// Thermo Sudoku
+///
+/// The numbers on a thermometer increase from its bulb. Every thermometer is
+/// written in its own line of the `thermo` section, starting with the bulb:
+/// ```text
+/// thermo:
+/// r1c1 r1c2 r1c3
+/// r5c5 r6c6;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThermoSudoku {
+    thermos: Vec<Vec<(usize, usize)>>,
+}
+
+impl SudokuRule for ThermoSudoku {
+    fn str_identifier() -> &'static str {
+        "thermo"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        let mut thermos = vec![];
+        for line in section_content(input, Self::str_identifier()).lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let cells = parse_cells(line, geometry.size()).ok_or_else(|| {
+                invalid_section(Self::str_identifier(), format!("Invalid cell in {}", line))
+            })?;
+            if cells.len() < 2 || cells.len() > geometry.size() {
+                return Err(invalid_section(
+                    Self::str_identifier(),
+                    "A thermometer needs at least two cells and at most one for every number",
+                ));
+            }
+            if (1..cells.len()).any(|i| cells[..i].contains(&cells[i])) {
+                return Err(invalid_section(
+                    Self::str_identifier(),
+                    "A thermometer can't contain a cell twice",
+                ));
+            }
+
+            thermos.push(cells);
+        }
+
+        Ok(Box::new(Self { thermos }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        let size = field.len() as i32;
+
+        for thermo in &self.thermos {
+            let mut last: Option<(usize, i32)> = None;
+            for (index, &(row, collum)) in thermo.iter().enumerate() {
+                if field[row][collum].count_ones() != 1 {
+                    continue;
+                }
+
+                // Every cell before needs a smaller number and every cell after a bigger one
+                let number = field[row][collum].trailing_zeros() as i32 + 1;
+                let before = index as i32;
+                let after = (thermo.len() - index - 1) as i32;
+                if number <= before || number + after > size {
+                    return false;
+                }
+                if let Some((last_index, last_number)) = last {
+                    if number - last_number < (index - last_index) as i32 {
+                        return false;
+                    }
+                }
+
+                last = Some((index, number));
+            }
+        }
+
+        true
+    }
+
+    fn get_not_possible_numbers_raw(&self, field: &[Vec<u32>], row: usize, collum: usize) -> u32 {
+        let size = field.len() as i32;
+        let mut possible = all_numbers(field);
+
+        for thermo in &self.thermos {
+            let Some(position) = thermo.iter().position(|&cell| cell == (row, collum)) else {
+                continue;
+            };
+
+            // The smallest number of a cell before and the biggest number of a cell after limit the cell
+            let mut min = position as i32 + 1;
+            let mut max = size - (thermo.len() - position - 1) as i32;
+            for (index, &(r, c)) in thermo.iter().enumerate() {
+                let candidates = candidates(field, r, c);
+                if candidates == 0 || index == position {
+                    continue;
+                }
+
+                let distance = index as i32 - position as i32;
+                if index < position {
+                    min = min.max(candidates.trailing_zeros() as i32 + 1 - distance);
+                } else {
+                    max = max.min(candidates.ilog2() as i32 + 1 - distance);
+                }
+            }
+
+            let mut possible_on_thermo = 0;
+            for num in min.max(1)..=max.min(size) {
+                possible_on_thermo |= 0b1 << (num - 1);
+            }
+            possible &= possible_on_thermo;
+        }
+
+        all_numbers(field) & !possible
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        for thermo in &self.thermos {
+            drawing.circle(Drawing::center(thermo[0]), 0.35, "#c8c8c8", "none");
+            drawing.cell_line(thermo, "#c8c8c8", 0.3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod thermo_sudoku_test {
+    use super::ThermoSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let thermo = ThermoSudoku::from_str("thermo:\nr1c1 r1c2 r1c3", Geometry::CLASSIC);
+        let mut field = vec![vec![0; 9]; 9];
+
+        field[0][0] = 0b10;
+        field[2][0] = 0b1_000;
+        assert!(thermo.complys(&field));
+
+        // 2 _ 3 leaves no number for the middle cell
+        field[2][0] = 0b100;
+        assert!(!thermo.complys(&field));
+
+        // The bulb needs two bigger numbers after it
+        field[0][0] = 0b100_000_000;
+        field[2][0] = 0;
+        assert!(!thermo.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let thermo = ThermoSudoku::from_str("thermo:\nr1c1 r1c2 r1c3", Geometry::CLASSIC);
+        let mut field = vec![vec![0; 9]; 9];
+
+        assert_eq!(thermo.get_not_possible_numbers_raw(&field, 0, 0), 0b110_000_000);
+        assert_eq!(thermo.get_not_possible_numbers_raw(&field, 1, 0), 0b100_000_001);
+
+        // With a 3 in the bulb the middle has to be 4 to 8
+        field[0][0] = 0b100;
+        assert_eq!(thermo.get_not_possible_numbers_raw(&field, 1, 0), 0b100_000_111);
+
+        // With a 6 at the end the middle has to be 4 or 5
+        field[2][0] = 0b100_000;
+        assert_eq!(thermo.get_not_possible_numbers_raw(&field, 1, 0), 0b111_100_111);
+
+        // Cells not on the thermometer are not affected
+        assert_eq!(thermo.get_not_possible_numbers_raw(&field, 4, 4), 0);
+    }
+}
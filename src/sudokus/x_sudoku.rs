@@ -1,5 +1,12 @@
-use super::sudoku_rule::SudokuRule;
-
+use super::drawing::Drawing;
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{marker_section, SudokuRule};
+
+/// X-Sudoku
+///
+/// Both diagonals have to contain every number once.
+/// It is enabled with the marker `x-sudoku;`
 #[derive(Debug, Clone)]
 pub struct XSudoku {}
 
@@ -8,15 +15,17 @@ impl SudokuRule for XSudoku {
         "x-sudoku"
     }
 
-    fn from_str(_input: &str) -> Box<Self> {
-        Box::new(Self {})
+    fn try_from_str(input: &str, _geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        marker_section(input, Self::str_identifier())?;
+        Ok(Box::new(Self {}))
     }
 
-    fn complys(&self, field: &[[i32; 9]; 9]) -> bool {
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        let size = field.len();
         let mut tl_to_br = 0;
         let mut tr_to_bl = 0;
 
-        for i in 0..9 {
+        for i in 0..size {
             if field[i][i].count_ones() == 1 {
                 if tl_to_br & field[i][i] != 0 {
                     return false;
@@ -25,55 +34,35 @@ impl SudokuRule for XSudoku {
                 tl_to_br |= field[i][i];
             }
 
-            if field[8 - i][i].count_ones() == 1 {
-                if tr_to_bl & field[8- i][i] != 0 {
+            if field[size - 1 - i][i].count_ones() == 1 {
+                if tr_to_bl & field[size - 1 - i][i] != 0 {
                     return false;
                 }
 
-                tr_to_bl |= field[8 - i][i];
+                tr_to_bl |= field[size - 1 - i][i];
             }
         }
         true
     }
 
-    fn get_not_possible_numbers_raw(
-        &self,
-        field: &[[i32; 9]; 9],
-        row: usize,
-        collum: usize,
-    ) -> i32 {
-        // If the cell is not on the x their is no number that is not possible
-        if row != collum || row != 9 - collum {
-            return 0;
-        }
-        let mut tl_to_br = 0;
-        let mut tr_to_bl = 0;
-
-        for i in 0..9 {
-            if field[i][i].count_ones() == 1 {
-                tl_to_br |= field[i][i];
-            }
-
-            if field[8 - i][i].count_ones() == 1 {
-                tr_to_bl |= field[8 - i][i];
-            }
-        }
-
-        if row == 4 && collum == 4 {
-            return tr_to_bl | tl_to_br;
-        }
-
-        if row == collum {
-            return tl_to_br;
-        }
+    fn houses(&self, size: usize) -> Vec<Vec<(usize, usize)>> {
+        vec![
+            (0..size).map(|i| (i, i)).collect(),
+            (0..size).map(|i| (size - 1 - i, i)).collect(),
+        ]
+    }
 
-        tr_to_bl
+    fn draw(&self, drawing: &mut Drawing) {
+        let size = drawing.geometry().size() as f64;
+        drawing.line(&[(0.0, 0.0), (size, size)], "#a0a0a0", 0.03);
+        drawing.line(&[(size, 0.0), (0.0, size)], "#a0a0a0", 0.03);
     }
 }
 
 #[cfg(test)]
 mod x_sudoku_test {
     use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
     use super::XSudoku;
 
     #[test]
@@ -99,9 +88,20 @@ mod x_sudoku_test {
         [6,4,5,3,1,2,9,7,8],
         [9,7,8,6,4,5,3,1,2]];
 
-        let x = XSudoku::from_str("");
-        assert!(x.complys(&good_field));
-        assert!(!x.complys(&bad_field));
+        let raw = |field: [[u32; 9]; 9]| field.map(|r| r.map(|n| 0b1 << (n - 1)).to_vec()).to_vec();
+
+        let x = XSudoku::from_str("", Geometry::CLASSIC);
+        assert!(x.complys(&raw(good_field)));
+        assert!(!x.complys(&raw(bad_field)));
         
     }
+
+    #[test]
+    fn houses() {
+        let houses = XSudoku::from_str("", Geometry::CLASSIC).houses(9);
+
+        assert_eq!(houses.len(), 2);
+        assert!(houses[0].contains(&(4, 4)) && houses[1].contains(&(4, 4)));
+        assert!(houses[1].contains(&(8, 0)) && houses[1].contains(&(0, 8)));
+    }
 }
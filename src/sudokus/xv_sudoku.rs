@@ -0,0 +1,127 @@
+use super::drawing::Drawing;
+use super::edge_marks::{EdgeMark, EdgeMarks};
+use super::geometry::Geometry;
+use super::parse_error::ParseError;
+use super::sudoku_rule::{all_numbers, section_content, SudokuRule};
+
+/// Signs between two cells of a XV Sudoku
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Sign {
+    /// The numbers sum up to 10
+    X,
+    /// The numbers sum up to 5
+    V,
+}
+
+impl EdgeMark for Sign {
+    const ALL: &'static [Self] = &[Sign::X, Sign::V];
+
+    fn name(self) -> &'static str {
+        match self {
+            Sign::X => "x",
+            Sign::V => "v",
+        }
+    }
+
+    fn related(self, first: i32, second: i32) -> bool {
+        match self {
+            Sign::X => first + second == 10,
+            Sign::V => first + second == 5,
+        }
+    }
+
+    fn draw(self, drawing: &mut Drawing, (x, y): (f64, f64)) {
+        let text = match self {
+            Sign::X => "X",
+            Sign::V => "V",
+        };
+        drawing.rect((x - 0.14, y - 0.16), (0.28, 0.32), "#ffffff", "none");
+        drawing.text((x, y), text, 0.35, "#000000");
+    }
+}
+
+/// XV Sudoku
+///
+/// For the data structure see `EdgeMarks`:
+/// ```text
+/// xv:
+/// x: r1c2-r1c3 r2c5-r3c5
+/// v: r2c8-r2c9
+/// negative;
+/// ```
+#[derive(Debug, Clone)]
+pub struct XVSudoku {
+    signs: EdgeMarks<Sign>,
+}
+
+impl SudokuRule for XVSudoku {
+    fn str_identifier() -> &'static str {
+        "xv"
+    }
+
+    fn try_from_str(input: &str, geometry: Geometry) -> Result<Box<Self>, ParseError> {
+        Ok(Box::new(Self {
+            signs: EdgeMarks::from_str(
+                section_content(input, Self::str_identifier()),
+                Self::str_identifier(),
+                geometry.size(),
+            )?,
+        }))
+    }
+
+    fn complys(&self, field: &[Vec<u32>]) -> bool {
+        self.signs.complys(field)
+    }
+
+    fn get_not_possible_numbers_raw(
+        &self,
+        field: &[Vec<u32>],
+        row: usize,
+        collum: usize,
+    ) -> u32 {
+        all_numbers(field) & !self.signs.possible_numbers(field, row, collum)
+    }
+
+    fn draw(&self, drawing: &mut Drawing) {
+        self.signs.draw(drawing);
+    }
+}
+
+#[cfg(test)]
+mod xv_sudoku_test {
+    use super::XVSudoku;
+    use crate::sudokus::sudoku_rule::SudokuRule;
+    use crate::sudokus::Geometry;
+
+    #[test]
+    fn comply() {
+        let mut field = vec![vec![0; 9]; 9];
+        // 3 7 2 in the first line
+        field[0][0] = 0b100;
+        field[1][0] = 0b1_000_000;
+        field[2][0] = 0b10;
+
+        let good = XVSudoku::from_str("xv:\nx: r1c1-r1c2\nnegative", Geometry::CLASSIC);
+        let wrong_sign = XVSudoku::from_str("xv:\nv: r1c1-r1c2", Geometry::CLASSIC);
+        let missing = XVSudoku::from_str("xv:\nnegative", Geometry::CLASSIC);
+
+        assert!(good.complys(&field));
+        assert!(!wrong_sign.complys(&field));
+        assert!(!missing.complys(&field));
+    }
+
+    #[test]
+    fn not_possible() {
+        let mut field = vec![vec![0; 9]; 9];
+        field[0][0] = 0b100;
+
+        let xv = XVSudoku::from_str("xv:\nx: r1c1-r1c2\nv: r1c1-r2c1\nnegative", Geometry::CLASSIC);
+
+        assert_eq!(xv.get_not_possible_numbers_raw(&field, 1, 0), 0b110_111_111);
+        assert_eq!(xv.get_not_possible_numbers_raw(&field, 0, 1), 0b111_111_101);
+
+        // Without a sign next to a 3 it can't be 2 or 7
+        field[1][1] = 0b100;
+        assert_eq!(xv.get_not_possible_numbers_raw(&field, 2, 1), 0b001_000_010);
+    }
+}
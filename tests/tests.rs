@@ -2,7 +2,9 @@
 #[cfg(test)]
 mod impl_sudoku_test {
 
-    use sudoku::sudokus::{Sudoku, FastSudoku};
+    use std::fs;
+
+    use sudoku::sudokus::{Sudoku, FastSudoku, FileFormat, MultiSudoku};
 
     
     #[test]
@@ -24,6 +26,89 @@ mod impl_sudoku_test {
         }
     }
 
+    #[test]
+    fn solve_lines() {
+        let path = "test-sudokus/lines/collection-26-10-18-unsolved";
+
+        let sudokus = Sudoku::lines_from_file(path).unwrap();
+        let fast_sudokus = FastSudoku::lines_from_file(path).unwrap();
+        let mut count = 0;
+        for (sudoku, fast_sudoku) in sudokus.zip(fast_sudokus) {
+            let mut sudoku = sudoku.unwrap();
+            let mut fast_sudoku = fast_sudoku.unwrap();
+            assert_eq!(sudoku.to_line(), fast_sudoku.to_line());
+
+            sudoku.solve();
+            fast_sudoku.solve();
+            assert!(sudoku.is_correct());
+            assert!(sudoku.is_solved());
+            assert_eq!(sudoku.to_line(), fast_sudoku.to_line());
+            count += 1;
+        }
+        assert_eq!(count, 7);
+    }
+
+    #[test]
+    fn file_formats() {
+        let hard = Sudoku::from_file("test-sudokus/text/welt-23-11-11-hard-unsolved");
+        for path in [
+            "test-sudokus/formats/welt-23-11-11-hard-unsolved.sdk",
+            "test-sudokus/formats/welt-23-11-11-hard-unsolved.ss",
+        ] {
+            assert_eq!(Sudoku::from_file_with_format(path).unwrap(), hard);
+        }
+        for format in [FileFormat::Sdk, FileFormat::Sdm, FileFormat::Ss, FileFormat::SadMan] {
+            assert_eq!(Sudoku::parse_format(&hard.to_format(format), format).unwrap(), hard);
+        }
+
+        let mut sudoku = Sudoku::from_file_with_format("test-sudokus/formats/sudoku-com-12-11-12-master-unsolved-sadman.sdk").unwrap();
+        assert_eq!(sudoku.get_number(0, 0), 3);
+        assert!(sudoku.solve());
+        assert_eq!(sudoku, Sudoku::from_file("test-sudokus/text/sudoku-com-12-11-12-master-solved"));
+    }
+
+    #[test]
+    fn f_puzzles() {
+        let paths = [
+            ["test-sudokus/f-puzzles/constructed-26-10-18-thermo-hard-unsolved.json", "test-sudokus/text/constructed-26-10-18-thermo-hard-solved"],
+            ["test-sudokus/f-puzzles/constructed-26-10-18-killer-hard-unsolved-link", "test-sudokus/text/constructed-26-10-18-killer-hard-solved"],
+            ["test-sudokus/f-puzzles/sudokuzok-de-24-01-07-evenodd-easy-unsolved-sudokupad-link", "test-sudokus/text/sudokuzok-de-24-01-07-evenodd-easy-solved"],
+            ["test-sudokus/f-puzzles/sudoku-com-24-01-06-x-sudoku-easy-unsolved.json", "test-sudokus/text/sudoku-com-24-01-06-x-sudoku-easy-solved"],
+        ];
+
+        for path in paths {
+            let mut sudoku = Sudoku::from_f_puzzles(&fs::read_to_string(path[0]).unwrap()).unwrap();
+            assert!(sudoku.solve());
+            assert_eq!(sudoku, Sudoku::from_file(path[1]));
+        }
+    }
+
+    #[test]
+    fn pictures() {
+        let mut paths: Vec<_> = fs::read_dir("test-sudokus/pics").unwrap().map(|entry| entry.unwrap().path()).collect();
+        paths.sort();
+        assert_eq!(paths.len(), 7);
+
+        for path in paths {
+            let sudoku = Sudoku::from_picture(path.to_str().unwrap()).unwrap();
+            let text = format!("test-sudokus/text/{}", path.file_stem().unwrap().to_str().unwrap());
+            assert_eq!(sudoku.to_line(), Sudoku::from_file(&text).to_line(), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn rendered_pictures() {
+        // Rendered with the DejaVu fonts, the digits weren't traced from these pictures
+        for path in [
+            "test-sudokus/pics-rendered/sudoku-com-12-11-12-master-unsolved.png",
+            "test-sudokus/pics-rendered/sudoku-com-12-11-12-master-solved.png",
+        ] {
+            let sudoku = Sudoku::from_picture(path).unwrap();
+            let text = path.replace("pics-rendered", "text").replace(".png", "");
+            assert_eq!(sudoku.to_line(), Sudoku::from_file(&text).to_line(), "{}", path);
+        }
+    }
+
     #[test]
     fn file_cross_check() {
         for  path in Sudoku::SUDOKUS_WITH_SOLUTION {
@@ -36,6 +121,38 @@ mod impl_sudoku_test {
         }
     }
 
+    #[test]
+    fn multi_file_cross_check() {
+        for path in MultiSudoku::SUDOKUS_WITH_SOLUTION {
+            let mut sudoku_unsolved = MultiSudoku::from_file(path[0]);
+            let sudoku_solved = MultiSudoku::from_file(path[1]);
+
+            assert!(sudoku_unsolved.solve());
+            assert!(sudoku_unsolved.is_correct());
+
+            assert_eq!(sudoku_solved, sudoku_unsolved);
+        }
+    }
+
+    #[test]
+    fn svg() {
+        for path in Sudoku::UNSOLVED_SUDOKU_FILES {
+            let mut sudoku = Sudoku::from_file(path);
+            let unsolved = sudoku.to_svg(false);
+            sudoku.solve();
+            let solved = sudoku.to_svg(true);
+
+            assert!(unsolved.starts_with("<svg") && unsolved.ends_with("</svg>\n"));
+            // Solved numbers have their own color and are only in the drawing of the solution
+            assert!(!unsolved.contains("#1a56c4"));
+            let size = sudoku.geometry().size();
+            let solved_cells = (0..size * size)
+                .filter(|count| !sudoku.is_given(count % size, count / size) && sudoku.is_set(count % size, count / size))
+                .count();
+            assert_eq!(solved.matches("#1a56c4").count(), solved_cells);
+        }
+    }
+
     #[test]
     fn implementation_cross_check() {
 
@@ -47,7 +164,11 @@ mod impl_sudoku_test {
             fast_sudoku.solve();
             sudoku.solve();
 
-            assert_eq!(fast_sudoku.field, sudoku.field);
+            for row in 0..9 {
+                for collum in 0..9 {
+                    assert_eq!(fast_sudoku.field[row][collum] as u32, sudoku.field[row][collum]);
+                }
+            }
         }
 
     }